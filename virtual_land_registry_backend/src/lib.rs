@@ -1,29 +1,122 @@
 use candid::{CandidType, Deserialize, Principal};
-use ic_cdk::api::time;
+use system::time;
 use ic_cdk::{init, query, update, pre_upgrade, post_upgrade};
+use ic_certification::{AsHashTree, HashTree, HashTreeNode, RbTree};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Bound;
+
+// System API, routed through one place so unit tests can run natively against a
+// fake caller and clock
+#[cfg(not(test))]
+mod system {
+    use candid::Principal;
+    
+    pub fn caller() -> Principal {
+        ic_cdk::caller()
+    }
+    
+    pub fn id() -> Principal {
+        ic_cdk::id()
+    }
+    
+    pub fn time() -> u64 {
+        ic_cdk::api::time()
+    }
+    
+    pub fn set_certified_data(data: &[u8]) {
+        ic_cdk::api::set_certified_data(data);
+    }
+    
+    pub fn data_certificate() -> Option<Vec<u8>> {
+        ic_cdk::api::data_certificate()
+    }
+    
+    pub fn set_global_timer(timestamp: u64) {
+        ic_cdk::api::set_global_timer(timestamp);
+    }
+}
+
+#[cfg(test)]
+mod system {
+    use candid::Principal;
+    use std::cell::RefCell;
+    
+    thread_local! {
+        static CALLER: RefCell<Principal> = const { RefCell::new(Principal::anonymous()) };
+        static NOW: RefCell<u64> = const { RefCell::new(1_000_000_000) };
+        static CERTIFIED_DATA: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+        static GLOBAL_TIMER: RefCell<u64> = const { RefCell::new(0) };
+    }
+    
+    pub fn caller() -> Principal {
+        CALLER.with(|caller| *caller.borrow())
+    }
+    
+    pub fn id() -> Principal {
+        Principal::from_slice(&[0xCA; 10])
+    }
+    
+    pub fn time() -> u64 {
+        NOW.with(|now| *now.borrow())
+    }
+    
+    pub fn set_certified_data(data: &[u8]) {
+        CERTIFIED_DATA.with(|certified| *certified.borrow_mut() = data.to_vec());
+    }
+    
+    pub fn data_certificate() -> Option<Vec<u8>> {
+        None
+    }
+    
+    pub fn set_global_timer(timestamp: u64) {
+        GLOBAL_TIMER.with(|timer| *timer.borrow_mut() = timestamp);
+    }
+    
+    pub fn set_caller(principal: Principal) {
+        CALLER.with(|caller| *caller.borrow_mut() = principal);
+    }
+    
+    pub fn advance_time(ns: u64) {
+        NOW.with(|now| *now.borrow_mut() += ns);
+    }
+    
+    pub fn certified_data() -> Vec<u8> {
+        CERTIFIED_DATA.with(|certified| certified.borrow().clone())
+    }
+}
 
 // Type definitions
 type LandId = u64;
+type DistrictId = u64;
+type RezoningRequestId = u64;
+type AdminProposalId = u64;
+type GovernanceProposalId = u64;
+type AuditEntryId = u64;
+type HoldId = u64;
+type DisputeId = u64;
+type RecoveryRequestId = u64;
+type OfferId = u64;
 type Price = u64; // in cycles
 type Timestamp = u64;
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
 pub struct Coordinates {
     pub x: i32,
     pub y: i32,
     pub z: i32,
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
 pub struct Dimensions {
     pub width: u32,
     pub height: u32,
     pub depth: u32,
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum LandType {
     Residential,
     Commercial,
@@ -33,15 +126,30 @@ pub enum LandType {
     Mixed,
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum AttributeValue {
+    Enum(String),
+    Number(i64),
+    Bool(bool),
+    Text(String),
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub struct LandAttribute {
+    pub key: String,
+    pub value: AttributeValue,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
 pub struct LandMetadata {
     pub environment: Option<String>,
     pub special_features: Vec<String>,
     pub access_roads: Vec<String>,
     pub utilities: Vec<String>,
+    pub attributes: Option<Vec<LandAttribute>>,
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
 pub struct LandInfo {
     pub id: LandId,
     pub owner: Principal,
@@ -52,6 +160,28 @@ pub struct LandInfo {
     pub metadata: Option<LandMetadata>,
     pub created_at: Timestamp,
     pub last_updated: Timestamp,
+    // Optional so that clients built before holds existed still decode parcels; None when
+    // the parcel has no holds
+    pub holds: Option<Vec<LandHold>>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum HoldKind {
+    Lien,
+    Freeze,
+    Dispute,
+}
+
+// While active, a hold blocks transfers, sales, listings and metadata changes
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub struct LandHold {
+    pub id: HoldId,
+    pub kind: HoldKind,
+    pub reason: String,
+    pub dispute_id: Option<DisputeId>,
+    pub placed_by: Principal,
+    pub placed_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
@@ -63,6 +193,17 @@ pub struct LandRegistration {
     pub metadata: Option<LandMetadata>,
 }
 
+// Stored form of a listing; the land itself is joined in at read time
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ListingRecord {
+    pub land_id: LandId,
+    pub seller: Principal,
+    // The owner, or the operator who listed on the owner's behalf
+    pub listed_by: Principal,
+    pub price: Price,
+    pub listed_at: Timestamp,
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize)]
 pub struct MarketplaceListing {
     pub land_id: LandId,
@@ -70,6 +211,7 @@ pub struct MarketplaceListing {
     pub price: Price,
     pub listed_at: Timestamp,
     pub land_info: LandInfo,
+    pub version: u64,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
@@ -87,9 +229,14 @@ pub enum TransactionType {
     Registration,
     Transfer,
     Sale,
+    Rezoning,
+    Removal { reason: String },
+    Restoration,
+    ForcedTransfer { dispute_id: DisputeId },
+    Recovery { request_id: RecoveryRequestId },
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
 pub struct LandStatistics {
     pub total_lands: u64,
     pub total_owners: u64,
@@ -98,7 +245,7 @@ pub struct LandStatistics {
     pub total_transactions: u64,
 }
 
-#[derive(Clone, Debug, CandidType, Deserialize)]
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
 pub struct SearchFilters {
     pub land_type: Option<LandType>,
     pub min_price: Option<Price>,
@@ -106,6 +253,637 @@ pub struct SearchFilters {
     pub coordinates_range: Option<(Coordinates, Coordinates)>,
     pub min_area: Option<u32>,
     pub features: Option<Vec<String>>,
+    pub owner: Option<Principal>,
+    pub created_after: Option<Timestamp>,
+    pub created_before: Option<Timestamp>,
+    pub max_area: Option<u32>,
+    pub environment: Option<String>,
+    pub utilities: Option<Vec<String>>,
+    pub access_roads: Option<Vec<String>>,
+    pub for_sale_only: Option<bool>,
+    pub text: Option<String>,
+    pub attributes: Option<Vec<LandAttribute>>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ZoningRules {
+    pub allowed_land_types: Vec<LandType>,
+    pub max_parcel_area: Option<u32>,
+    pub max_build_height: Option<u32>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct DistrictDefinition {
+    pub name: String,
+    pub min_coords: Coordinates,
+    pub max_coords: Coordinates,
+    pub zoning: ZoningRules,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct District {
+    pub id: DistrictId,
+    pub name: String,
+    pub min_coords: Coordinates,
+    pub max_coords: Coordinates,
+    pub zoning: ZoningRules,
+    pub created_at: Timestamp,
+    pub last_updated: Timestamp,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum RezoningStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct RezoningRequest {
+    pub id: RezoningRequestId,
+    pub land_id: LandId,
+    pub requester: Principal,
+    pub current_type: LandType,
+    pub new_type: LandType,
+    pub reason: String,
+    pub status: RezoningStatus,
+    pub created_at: Timestamp,
+    pub resolved_at: Option<Timestamp>,
+    pub resolved_by: Option<Principal>,
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct MapTile {
+    pub tile_x: u32,
+    pub tile_y: u32,
+    pub land_id: LandId,
+    pub land_type: LandType,
+    pub for_sale: bool,
+    pub owner_hash: u32,
+    pub land_count: u32,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct MapChunk {
+    pub chunk_x: i32,
+    pub chunk_y: i32,
+    pub zoom: u8,
+    pub tile_size: u32,
+    pub origin_x: i64,
+    pub origin_y: i64,
+    pub tiles: Vec<MapTile>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct CertifiedOwner {
+    pub owner: Option<Principal>,
+    pub certificate: Option<Vec<u8>>,
+    pub witness: Vec<u8>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+// Only the price is certified, so nothing else about the listing is returned with the witness
+pub struct CertifiedListing {
+    pub land_id: LandId,
+    pub price: Option<Price>,
+    pub certificate: Option<Vec<u8>>,
+    pub witness: Vec<u8>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum SortKey {
+    Id,
+    Price,
+    Area,
+    ListedAt,
+    CreatedAt,
+    Relevance,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+// Position of the last item returned: its sort value and id
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct PageCursor {
+    pub sort_value: u64,
+    pub id: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct PageRequest {
+    pub cursor: Option<PageCursor>,
+    pub limit: Option<u32>,
+    pub sort_by: Option<SortKey>,
+    pub direction: Option<SortDirection>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<PageCursor>,
+    pub total_count: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub enum AttributeKind {
+    Enum(Vec<String>),
+    Number { min: Option<i64>, max: Option<i64> },
+    Bool,
+    Text { max_length: Option<u32> },
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct AttributeSchema {
+    pub key: String,
+    pub label: String,
+    pub kind: AttributeKind,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct FacetCount {
+    pub key: String,
+    pub value: String,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct FacetedLandPage {
+    pub page: Page<LandInfo>,
+    pub facets: Vec<FacetCount>,
+}
+
+// One page of an export; pass `next_cursor` back to continue
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GeoJsonExport {
+    pub geojson: String,
+    pub next_cursor: Option<PageCursor>,
+    pub total_count: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GltfExport {
+    pub glb: Vec<u8>,
+    pub next_cursor: Option<PageCursor>,
+    pub total_count: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct InvariantIssue {
+    pub store: String,
+    pub land_id: Option<LandId>,
+    pub description: String,
+}
+
+// Parcels registered before a zoning or schema change are grandfathered, so those
+// mismatches are warnings; only structural errors refuse a snapshot
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct SnapshotValidation {
+    pub errors: Vec<InvariantIssue>,
+    pub warnings: Vec<InvariantIssue>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RepairReport {
+    pub found: Vec<InvariantIssue>,
+    pub remaining: Vec<InvariantIssue>,
+}
+
+// SuperAdmin implicitly holds every other role
+#[derive(Clone, Copy, Debug, CandidType, Deserialize, Serialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Role {
+    SuperAdmin,
+    Moderator,
+    Treasurer,
+    ZoningOfficer,
+    SupportAgent,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RoleAssignment {
+    pub principal: Principal,
+    pub roles: Vec<Role>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RoleChange {
+    pub principal: Principal,
+    pub role: Role,
+    pub granted: bool,
+    pub actor: Principal,
+    pub timestamp: Timestamp,
+}
+
+// Destructive or sensitive operations that need M-of-N approval
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum AdminAction {
+    RemoveLand { land_id: LandId, reason: String },
+    RestoreLands(Vec<LandInfo>),
+    CommitSnapshotImport { checksum: Vec<u8> },
+    SetApprovalThreshold(u32),
+    GrantRole { principal: Principal, role: Role },
+    RevokeRole { principal: Principal, role: Role },
+    ForceTransfer { dispute_id: DisputeId, new_owner: Principal, note: Option<String> },
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum AdminProposalStatus {
+    Pending,
+    Executed,
+    Failed,
+    Cancelled,
+    Expired,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct AdminProposal {
+    pub id: AdminProposalId,
+    pub action: AdminAction,
+    pub proposer: Principal,
+    pub approvals: Vec<Principal>,
+    pub required_approvals: u32,
+    pub status: AdminProposalStatus,
+    pub created_at: Timestamp,
+    pub expires_at: Timestamp,
+    pub resolved_at: Option<Timestamp>,
+    pub failure: Option<LandRegistryError>,
+}
+
+// Registry-wide settings owned by governance
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RegistryParameters {
+    pub world_min: Coordinates,
+    pub world_max: Coordinates,
+}
+
+impl Default for RegistryParameters {
+    fn default() -> Self {
+        RegistryParameters {
+            world_min: Coordinates { x: -1000000, y: -1000000, z: -1000 },
+            world_max: Coordinates { x: 1000000, y: 1000000, z: 1000 },
+        }
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, PartialEq)]
+pub enum VotingPowerBasis {
+    LandArea,
+    ParcelCount,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GovernanceConfig {
+    pub voting_power_basis: VotingPowerBasis,
+    // Share of total voting power that must take part, in basis points
+    pub quorum_bps: u32,
+    pub voting_period_ns: u64,
+    pub min_proposer_power: u64,
+}
+
+impl Default for GovernanceConfig {
+    fn default() -> Self {
+        GovernanceConfig {
+            voting_power_basis: VotingPowerBasis::LandArea,
+            quorum_bps: 2_000,
+            voting_period_ns: 7 * 24 * 60 * 60 * 1_000_000_000,
+            min_proposer_power: 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum GovernanceAction {
+    SetWorldBounds { world_min: Coordinates, world_max: Coordinates },
+    SetDistrictZoning { district_id: DistrictId, zoning: ZoningRules },
+    GrantRole { principal: Principal, role: Role },
+    RevokeRole { principal: Principal, role: Role },
+    SetGovernanceConfig(GovernanceConfig),
+    ApproveRezoning { request_id: RezoningRequestId },
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum GovernanceProposalStatus {
+    Open,
+    Executed,
+    Rejected,
+    Failed,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GovernanceVote {
+    pub voter: Principal,
+    pub approve: bool,
+    pub power: u64,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GovernanceProposal {
+    pub id: GovernanceProposalId,
+    pub proposer: Principal,
+    pub title: String,
+    pub description: String,
+    pub action: GovernanceAction,
+    pub votes: Vec<GovernanceVote>,
+    // Parcels whose power has been used, so a parcel votes once even if it changes hands
+    pub counted_lands: Vec<LandId>,
+    pub yes_power: u64,
+    pub no_power: u64,
+    pub quorum: u64,
+    pub created_at: Timestamp,
+    pub voting_ends_at: Timestamp,
+    pub status: GovernanceProposalStatus,
+    pub resolved_at: Option<Timestamp>,
+    pub failure: Option<LandRegistryError>,
+}
+
+#[derive(Clone, Copy, Debug, CandidType, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum PauseScope {
+    Registration,
+    Marketplace,
+    Transfers,
+    All,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct PauseState {
+    pub scope: PauseScope,
+    pub reason: String,
+    pub paused_at: Timestamp,
+    pub paused_by: Principal,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, PartialEq)]
+pub enum AuditAction {
+    LandRegistered,
+    LandTransferred,
+    LandSold,
+    LandListed,
+    LandDelisted,
+    MetadataUpdated,
+    LandRezoned,
+    LandRemoved,
+    LandRestored,
+    LandPurged,
+    LandsRestored,
+    SnapshotImported,
+    RegistryRepaired,
+    RoleGranted,
+    RoleRevoked,
+    DistrictCreated,
+    DistrictUpdated,
+    DistrictRemoved,
+    AttributeDefined,
+    AttributeRemoved,
+    RezoningRequested,
+    RezoningResolved,
+    AdminActionProposed,
+    AdminActionApproved,
+    AdminActionCancelled,
+    AdminActionResolved,
+    GovernanceProposalSubmitted,
+    GovernanceProposalResolved,
+    Paused,
+    Unpaused,
+    HoldPlaced,
+    HoldReleased,
+    DisputeOpened,
+    DisputeEvidenceSubmitted,
+    DisputeResolved,
+    LandForceTransferred,
+    OperatorGranted,
+    OperatorRevoked,
+    GuardiansUpdated,
+    RecoveryStarted,
+    RecoveryApproved,
+    RecoveryCancelled,
+    RecoveryExecuted,
+    OfferMade,
+    OfferWithdrawn,
+    OfferAccepted,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, PartialEq)]
+pub enum AuditTarget {
+    Land(LandId),
+    Principal(Principal),
+    District(DistrictId),
+    Attribute(String),
+    RezoningRequest(RezoningRequestId),
+    AdminProposal(AdminProposalId),
+    GovernanceProposal(GovernanceProposalId),
+    Pause(PauseScope),
+    Dispute(DisputeId),
+    RecoveryRequest(RecoveryRequestId),
+    Offer(OfferId),
+    Registry,
+}
+
+// `before` and `after` hold the JSON form of the affected record, where there is one
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct AuditEntry {
+    pub id: AuditEntryId,
+    pub actor: Principal,
+    pub action: AuditAction,
+    pub target: AuditTarget,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct AuditFilter {
+    pub actor: Option<Principal>,
+    pub action: Option<AuditAction>,
+    pub target: Option<AuditTarget>,
+    pub from: Option<Timestamp>,
+    pub to: Option<Timestamp>,
+}
+
+// A removed parcel, kept out of every query until it is restored or purged
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Tombstone {
+    pub land: LandInfo,
+    pub reason: String,
+    pub removed_by: Principal,
+    pub removed_at: Timestamp,
+    pub purge_after: Timestamp,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct DisputeEvidence {
+    pub submitted_by: Principal,
+    pub content: String,
+    pub submitted_at: Timestamp,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum DisputeOutcome {
+    Dismissed,
+    Upheld,
+    ForcedTransfer(Principal),
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Resolved,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct Dispute {
+    pub id: DisputeId,
+    pub land_id: LandId,
+    pub claimant: Principal,
+    // Owner of the parcel when the dispute was opened
+    pub respondent: Principal,
+    pub statement: String,
+    pub evidence: Vec<DisputeEvidence>,
+    pub status: DisputeStatus,
+    pub outcome: Option<DisputeOutcome>,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Principal>,
+    pub opened_at: Timestamp,
+    pub resolved_at: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum OperatorPermission {
+    EditMetadata,
+    ManageListing,
+    AcceptOffers,
+}
+
+// Rights an owner delegates on one parcel; void once the parcel changes hands
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct OperatorGrant {
+    pub land_id: LandId,
+    pub operator: Principal,
+    pub permissions: Vec<OperatorPermission>,
+    pub granted_by: Principal,
+    pub granted_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+}
+
+// A standing bid on a parcel, whether or not it is listed; dropped when the parcel changes
+// hands or is removed
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct Offer {
+    pub id: OfferId,
+    pub land_id: LandId,
+    pub buyer: Principal,
+    pub price: Price,
+    pub made_at: Timestamp,
+}
+
+// An owner's recovery setup: `threshold` of `guardians` may move the owner's parcels once
+// the owner has been inactive for `inactivity_period_ns`
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct GuardianConfig {
+    pub owner: Principal,
+    pub guardians: Vec<Principal>,
+    pub threshold: u32,
+    pub inactivity_period_ns: u64,
+    pub challenge_period_ns: u64,
+    pub updated_at: Timestamp,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq)]
+pub enum RecoveryStatus {
+    // Collecting guardian approvals
+    Pending,
+    // Threshold reached; the owner may still cancel until `executable_after`
+    Challenge,
+    Executed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct RecoveryRequest {
+    pub id: RecoveryRequestId,
+    pub owner: Principal,
+    pub new_owner: Principal,
+    pub approvals: Vec<Principal>,
+    pub required_approvals: u32,
+    pub status: RecoveryStatus,
+    pub started_at: Timestamp,
+    pub executable_after: Option<Timestamp>,
+    pub resolved_at: Option<Timestamp>,
+    pub recovered_lands: Vec<LandId>,
+}
+
+// Every primary store; derived indexes are rebuilt on import
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct RegistrySnapshot {
+    pub format_version: u32,
+    pub lands: Vec<LandInfo>,
+    pub listings: Vec<ListingRecord>,
+    pub transactions: Vec<TransactionRecord>,
+    pub roles: Vec<RoleAssignment>,
+    pub role_changes: Vec<RoleChange>,
+    pub next_land_id: LandId,
+    pub districts: Vec<District>,
+    pub next_district_id: DistrictId,
+    pub rezoning_requests: Vec<RezoningRequest>,
+    pub next_rezoning_request_id: RezoningRequestId,
+    pub attribute_schemas: Vec<AttributeSchema>,
+    pub approval_threshold: u32,
+    pub admin_proposals: Vec<AdminProposal>,
+    pub next_admin_proposal_id: AdminProposalId,
+    pub registry_parameters: RegistryParameters,
+    pub governance_config: GovernanceConfig,
+    pub governance_proposals: Vec<GovernanceProposal>,
+    pub next_governance_proposal_id: GovernanceProposalId,
+    pub pauses: Vec<PauseState>,
+    pub audit_log: Vec<AuditEntry>,
+    pub tombstones: Vec<Tombstone>,
+    pub next_hold_id: HoldId,
+    pub disputes: Vec<Dispute>,
+    pub next_dispute_id: DisputeId,
+    pub operators: Vec<OperatorGrant>,
+    pub guardians: Vec<GuardianConfig>,
+    pub last_activity: Vec<(Principal, Timestamp)>,
+    pub recovery_requests: Vec<RecoveryRequest>,
+    pub next_recovery_request_id: RecoveryRequestId,
+    pub offers: Vec<Offer>,
+    pub next_offer_id: OfferId,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct SnapshotManifest {
+    pub snapshot_id: u64,
+    pub format_version: u32,
+    pub total_bytes: u64,
+    pub chunk_count: u32,
+    pub chunk_size: u32,
+    pub checksum: Vec<u8>,
+    pub created_at: Timestamp,
+}
+
+// An import in progress: the manifest it was announced with and the chunks received so far
+struct SnapshotImport {
+    manifest: SnapshotManifest,
+    chunks: BTreeMap<u32, Vec<u8>>,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
@@ -119,44 +897,196 @@ pub enum LandRegistryError {
     LandNotForSale,
     OwnershipError,
     InvalidInput,
+    DistrictNotFound,
+    ZoningViolation,
+    RequestNotFound,
+    InvalidAttribute,
+    ListingChanged,
+    InvalidSnapshot,
+    LastSuperAdmin,
+    ApprovalRequired,
+    Paused,
+    LandHeld,
+    OwnerActive,
+    InsufficientApprovers,
+    ProposalExpired,
 }
 
 type LandRegistryResult<T> = Result<T, LandRegistryError>;
 
+// Secondary indexes derived from LAND_STORAGE and MARKETPLACE
+#[derive(Default, PartialEq)]
+struct SecondaryIndexes {
+    by_type: HashMap<LandType, BTreeSet<LandId>>,
+    for_sale_by_type: HashMap<LandType, BTreeSet<LandId>>,
+    by_price: BTreeSet<(Price, LandId)>,
+    by_listed_at: BTreeSet<(Timestamp, LandId)>,
+}
+
 // Storage
 thread_local! {
     static LAND_STORAGE: RefCell<HashMap<LandId, LandInfo>> = RefCell::new(HashMap::new());
     static OWNERSHIP_INDEX: RefCell<HashMap<Principal, HashSet<LandId>>> = RefCell::new(HashMap::new());
-    static MARKETPLACE: RefCell<HashMap<LandId, MarketplaceListing>> = RefCell::new(HashMap::new());
+    static MARKETPLACE: RefCell<HashMap<LandId, ListingRecord>> = RefCell::new(HashMap::new());
     static TRANSACTION_HISTORY: RefCell<Vec<TransactionRecord>> = RefCell::new(Vec::new());
     static COORDINATE_INDEX: RefCell<HashMap<(i32, i32, i32), LandId>> = RefCell::new(HashMap::new());
     static NEXT_LAND_ID: RefCell<LandId> = RefCell::new(0);
-    static ADMINS: RefCell<HashSet<Principal>> = RefCell::new(HashSet::new());
+    static ROLES: RefCell<HashMap<Principal, BTreeSet<Role>>> = RefCell::new(HashMap::new());
+    static ROLE_CHANGES: RefCell<Vec<RoleChange>> = const { RefCell::new(Vec::new()) };
+    static ADMIN_PROPOSALS: RefCell<BTreeMap<AdminProposalId, AdminProposal>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_ADMIN_PROPOSAL_ID: RefCell<AdminProposalId> = const { RefCell::new(0) };
+    // Approvals needed for an AdminAction; never more than the number of SuperAdmins
+    static APPROVAL_THRESHOLD: RefCell<u32> = const { RefCell::new(1) };
+    static REGISTRY_PARAMETERS: RefCell<RegistryParameters> = RefCell::new(RegistryParameters::default());
+    static GOVERNANCE_CONFIG: RefCell<GovernanceConfig> = RefCell::new(GovernanceConfig::default());
+    static GOVERNANCE_PROPOSALS: RefCell<BTreeMap<GovernanceProposalId, GovernanceProposal>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_GOVERNANCE_PROPOSAL_ID: RefCell<GovernanceProposalId> = const { RefCell::new(0) };
+    static PAUSES: RefCell<HashMap<PauseScope, PauseState>> = RefCell::new(HashMap::new());
+    // Append-only; an entry's id is its position
+    static AUDIT_LOG: RefCell<Vec<AuditEntry>> = const { RefCell::new(Vec::new()) };
+    static TOMBSTONES: RefCell<BTreeMap<LandId, Tombstone>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_HOLD_ID: RefCell<HoldId> = const { RefCell::new(0) };
+    static DISPUTES: RefCell<BTreeMap<DisputeId, Dispute>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_DISPUTE_ID: RefCell<DisputeId> = const { RefCell::new(0) };
+    static OPERATORS: RefCell<BTreeMap<LandId, HashMap<Principal, OperatorGrant>>> = const { RefCell::new(BTreeMap::new()) };
+    static GUARDIANS: RefCell<HashMap<Principal, GuardianConfig>> = RefCell::new(HashMap::new());
+    // Only tracked for owners with a guardian setup
+    static LAST_ACTIVITY: RefCell<HashMap<Principal, Timestamp>> = RefCell::new(HashMap::new());
+    static RECOVERY_REQUESTS: RefCell<BTreeMap<RecoveryRequestId, RecoveryRequest>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_RECOVERY_REQUEST_ID: RefCell<RecoveryRequestId> = const { RefCell::new(0) };
+    static OFFERS: RefCell<BTreeMap<OfferId, Offer>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_OFFER_ID: RefCell<OfferId> = const { RefCell::new(0) };
+    static SPATIAL_INDEX: RefCell<HashMap<(i32, i32), HashSet<LandId>>> = RefCell::new(HashMap::new());
+    static SECONDARY_INDEXES: RefCell<SecondaryIndexes> = RefCell::new(SecondaryIndexes::default());
+    // term -> land -> weighted term frequency
+    static TEXT_INDEX: RefCell<HashMap<String, HashMap<LandId, u32>>> = RefCell::new(HashMap::new());
+    static DISTRICTS: RefCell<BTreeMap<DistrictId, District>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_DISTRICT_ID: RefCell<DistrictId> = const { RefCell::new(0) };
+    static ATTRIBUTE_SCHEMAS: RefCell<HashMap<String, AttributeSchema>> = RefCell::new(HashMap::new());
+    static REZONING_REQUESTS: RefCell<BTreeMap<RezoningRequestId, RezoningRequest>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_REZONING_REQUEST_ID: RefCell<RezoningRequestId> = const { RefCell::new(0) };
+    static CERTIFIED_TREE: RefCell<CertifiedTree> = RefCell::new(empty_certified_tree());
+    // Encoded snapshot awaiting download, and an upload awaiting commit
+    static SNAPSHOT_EXPORT: RefCell<Option<(SnapshotManifest, Vec<u8>)>> = const { RefCell::new(None) };
+    static SNAPSHOT_IMPORT: RefCell<Option<SnapshotImport>> = const { RefCell::new(None) };
+    static NEXT_SNAPSHOT_ID: RefCell<u64> = const { RefCell::new(0) };
 }
 
+// Side length of a spatial index cell on the x/y plane
+const SPATIAL_CELL_SIZE: i32 = 1024;
+const MAX_FREE_SPACE_RESULTS: u32 = 100;
+const MAX_FREE_SPACE_CANDIDATES: usize = 10_000;
+// A map chunk is MAP_CHUNK_TILES x MAP_CHUNK_TILES tiles, each 2^zoom world units wide
+const MAP_CHUNK_TILES: i64 = 32;
+const MAX_MAP_ZOOM: u8 = 16;
+const TEXT_STOPWORDS: [&str; 12] = ["a", "an", "and", "at", "by", "for", "in", "of", "on", "or", "the", "with"];
+const DEFAULT_PAGE_LIMIT: u32 = 50;
+const MAX_PAGE_LIMIT: u32 = 500;
+// Exports carry whole documents per parcel, so pages stay well under the reply size limit
+const MAX_EXPORT_PAGE_LIMIT: u32 = 200;
+const SNAPSHOT_FORMAT_VERSION: u32 = 16;
+// Stays well below the 2MiB ingress/response limit
+const SNAPSHOT_CHUNK_SIZE: u32 = 1_000_000;
+const ADMIN_PROPOSAL_WINDOW_NS: u64 = 3 * 24 * 60 * 60 * 1_000_000_000;
+const MAX_DISPUTE_TEXT_LENGTH: usize = 10_000;
+const MAX_OPEN_DISPUTES_PER_CLAIMANT: usize = 5;
+const MAX_DISPUTE_EVIDENCE: usize = 50;
+const MAX_GUARDIANS: usize = 10;
+const MIN_INACTIVITY_PERIOD_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+const MIN_CHALLENGE_PERIOD_NS: u64 = 3 * 24 * 60 * 60 * 1_000_000_000;
+const TOMBSTONE_RETENTION_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+const MIN_VOTING_PERIOD_NS: u64 = 60 * 60 * 1_000_000_000;
+const MAX_PROPOSAL_TITLE_LENGTH: usize = 200;
+const MAX_PROPOSAL_DESCRIPTION_LENGTH: usize = 10_000;
+
 #[init]
 fn init() {
-    let caller = ic_cdk::caller();
-    ADMINS.with(|admins| {
-        admins.borrow_mut().insert(caller);
-    });
+    let caller = system::caller();
+    assign_role(caller, Role::SuperAdmin, caller);
+    rebuild_certified_data();
 }
 
+// State crosses an upgrade as a full snapshot in stable memory
 #[pre_upgrade]
 fn pre_upgrade() {
-    // In a real implementation, you'd serialize state here
+    ic_cdk::storage::stable_save((capture_snapshot(),)).expect("failed to save registry state");
 }
 
 #[post_upgrade]
 fn post_upgrade() {
-    // In a real implementation, you'd deserialize state here
+    // Versions before the snapshot was persisted left stable memory empty
+    if ic_cdk::api::stable::stable_size() > 0 {
+        let (snapshot,): (RegistrySnapshot,) = ic_cdk::storage::stable_restore()
+            .expect("failed to restore registry state");
+        apply_snapshot(snapshot);
+    }
+    rebuild_certified_data();
+    // Timers do not survive an upgrade
+    schedule_next_timer();
+}
+
+// The canister's single system timer; fires at the earliest scheduled deadline
+#[export_name = "canister_global_timer"]
+extern "C" fn canister_global_timer() {
+    process_governance_deadlines();
+    purge_expired_tombstones();
+    release_expired_holds();
+    execute_due_recoveries();
+    schedule_next_timer();
+}
+
+fn schedule_next_timer() {
+    let next_vote_deadline = GOVERNANCE_PROPOSALS.with(|proposals| {
+        proposals.borrow().values()
+            .filter(|proposal| proposal.status == GovernanceProposalStatus::Open)
+            .map(|proposal| proposal.voting_ends_at)
+            .min()
+    });
+    let next_purge = TOMBSTONES.with(|tombstones| {
+        tombstones.borrow().values().map(|tombstone| tombstone.purge_after).min()
+    });
+    // Recoveries wait out a transfer pause; unpausing reschedules them
+    let transfers_paused = ensure_not_paused(PauseScope::Transfers).is_err();
+    let next_recovery = RECOVERY_REQUESTS.with(|requests| {
+        requests.borrow().values()
+            .filter(|request| !transfers_paused && request.status == RecoveryStatus::Challenge)
+            .filter_map(|request| request.executable_after)
+            .min()
+    });
+    let next_hold_expiry = LAND_STORAGE.with(|storage| {
+        storage.borrow().values()
+            .flat_map(|land| land.holds.iter().flatten())
+            .filter_map(|hold| hold.expires_at)
+            .min()
+    });
+    let next_deadline = [next_vote_deadline, next_purge, next_recovery, next_hold_expiry].into_iter().flatten().min();
+    // Zero cancels any pending timer
+    system::set_global_timer(next_deadline.unwrap_or(0));
 }
 
 // Utility functions
 fn validate_coordinates(coords: &Coordinates) -> bool {
-    coords.x >= -1000000 && coords.x <= 1000000 &&
-    coords.y >= -1000000 && coords.y <= 1000000 &&
-    coords.z >= -1000 && coords.z <= 1000
+    REGISTRY_PARAMETERS.with(|parameters| {
+        let parameters = parameters.borrow();
+        within_bounds(coords, &parameters.world_min, &parameters.world_max)
+    })
+}
+
+fn within_bounds(coords: &Coordinates, min: &Coordinates, max: &Coordinates) -> bool {
+    coords.x >= min.x && coords.x <= max.x &&
+    coords.y >= min.y && coords.y <= max.y &&
+    coords.z >= min.z && coords.z <= max.z
+}
+
+// Both the origin and the far corner of the parcel lie inside the bounds
+fn footprint_within_bounds(coords: &Coordinates, dims: &Dimensions, min: &Coordinates, max: &Coordinates) -> bool {
+    let far = |origin: i32, extent: u32| (origin as i64 + extent as i64 - 1).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    let far_corner = Coordinates {
+        x: far(coords.x, dims.width),
+        y: far(coords.y, dims.height),
+        z: far(coords.z, dims.depth),
+    };
+    within_bounds(coords, min, max) && within_bounds(&far_corner, min, max)
 }
 
 fn validate_dimensions(dims: &Dimensions) -> bool {
@@ -188,655 +1118,5195 @@ fn coordinates_overlap(
       z1_max <= z2_min || z2_max <= z1_min)
 }
 
-fn check_land_overlap(
+fn spatial_cell(x: i32, y: i32) -> (i32, i32) {
+    (x.div_euclid(SPATIAL_CELL_SIZE), y.div_euclid(SPATIAL_CELL_SIZE))
+}
+
+// Cells touched by the x/y footprint of a parcel
+fn spatial_cells(coords: &Coordinates, dims: &Dimensions) -> Vec<(i32, i32)> {
+    let (min_cx, min_cy) = spatial_cell(coords.x, coords.y);
+    let (max_cx, max_cy) = spatial_cell(
+        coords.x + dims.width as i32 - 1,
+        coords.y + dims.height as i32 - 1,
+    );
+    let mut cells = Vec::new();
+    for cx in min_cx..=max_cx {
+        for cy in min_cy..=max_cy {
+            cells.push((cx, cy));
+        }
+    }
+    cells
+}
+
+fn index_land_spatially(land: &LandInfo) {
+    SPATIAL_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        for cell in spatial_cells(&land.coordinates, &land.dimensions) {
+            index.entry(cell).or_insert_with(HashSet::new).insert(land.id);
+        }
+    });
+}
+
+fn unindex_land_spatially(land: &LandInfo) {
+    SPATIAL_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        for cell in spatial_cells(&land.coordinates, &land.dimensions) {
+            if let Some(lands) = index.get_mut(&cell) {
+                lands.remove(&land.id);
+                if lands.is_empty() {
+                    index.remove(&cell);
+                }
+            }
+        }
+    });
+}
+
+// Ids of lands whose footprint may intersect the given x/y cell range
+fn spatial_candidates(min_cell: (i32, i32), max_cell: (i32, i32)) -> HashSet<LandId> {
+    SPATIAL_INDEX.with(|index| {
+        let index = index.borrow();
+        let span = (max_cell.0 - min_cell.0 + 1) as u64 * (max_cell.1 - min_cell.1 + 1) as u64;
+        let mut candidates = HashSet::new();
+        if span <= index.len() as u64 {
+            for cx in min_cell.0..=max_cell.0 {
+                for cy in min_cell.1..=max_cell.1 {
+                    if let Some(lands) = index.get(&(cx, cy)) {
+                        candidates.extend(lands.iter().copied());
+                    }
+                }
+            }
+        } else {
+            // Region is larger than the populated part of the world, walk the index instead
+            for (cell, lands) in index.iter() {
+                if cell.0 >= min_cell.0 && cell.0 <= max_cell.0 &&
+                   cell.1 >= min_cell.1 && cell.1 <= max_cell.1 {
+                    candidates.extend(lands.iter().copied());
+                }
+            }
+        }
+        candidates
+    })
+}
+
+fn check_land_overlap(
     new_coords: &Coordinates,
     new_dims: &Dimensions,
 ) -> bool {
+    let cells = spatial_cells(new_coords, new_dims);
+    let candidates = spatial_candidates(cells[0], cells[cells.len() - 1]);
     LAND_STORAGE.with(|storage| {
-        storage.borrow().values().any(|land| {
-            coordinates_overlap(new_coords, new_dims, &land.coordinates, &land.dimensions)
-        })
+        let storage = storage.borrow();
+        candidates.iter()
+            .filter_map(|id| storage.get(id))
+            .any(|land| {
+                coordinates_overlap(new_coords, new_dims, &land.coordinates, &land.dimensions)
+            })
     })
 }
 
-// Public functions
+// Districts span from min_coords (inclusive) to max_coords (exclusive), like parcels do
+fn district_intersects(district: &District, coords: &Coordinates, dims: &Dimensions) -> bool {
+    let district_dims = Dimensions {
+        width: (district.max_coords.x - district.min_coords.x) as u32,
+        height: (district.max_coords.y - district.min_coords.y) as u32,
+        depth: (district.max_coords.z - district.min_coords.z) as u32,
+    };
+    coordinates_overlap(&district.min_coords, &district_dims, coords, dims)
+}
 
-#[update]
-fn register_land(registration: LandRegistration) -> LandRegistryResult<LandId> {
-    let caller = ic_cdk::caller();
-    
-    // Validate input
-    if !validate_coordinates(&registration.coordinates) {
-        return Err(LandRegistryError::InvalidCoordinates);
-    }
-    
-    if !validate_dimensions(&registration.dimensions) {
-        return Err(LandRegistryError::InvalidDimensions);
-    }
-    
-    if registration.description.trim().is_empty() {
+fn district_contains_point(district: &District, coords: &Coordinates) -> bool {
+    coords.x >= district.min_coords.x && coords.x < district.max_coords.x &&
+    coords.y >= district.min_coords.y && coords.y < district.max_coords.y &&
+    coords.z >= district.min_coords.z && coords.z < district.max_coords.z
+}
+
+fn zoning_permits(rules: &ZoningRules, dims: &Dimensions, land_type: &LandType) -> bool {
+    let type_allowed = rules.allowed_land_types.iter()
+        .any(|t| std::mem::discriminant(t) == std::mem::discriminant(land_type));
+    let area_allowed = rules.max_parcel_area
+        .map(|max| calculate_land_area(dims) <= max)
+        .unwrap_or(true);
+    let height_allowed = rules.max_build_height
+        .map(|max| dims.depth <= max)
+        .unwrap_or(true);
+    type_allowed && area_allowed && height_allowed
+}
+
+// A parcel must satisfy the zoning of every district it touches
+fn check_zoning(
+    coords: &Coordinates,
+    dims: &Dimensions,
+    land_type: &LandType,
+) -> LandRegistryResult<()> {
+    DISTRICTS.with(|districts| {
+        let violates = districts.borrow().values()
+            .filter(|district| district_intersects(district, coords, dims))
+            .any(|district| !zoning_permits(&district.zoning, dims, land_type));
+        if violates {
+            Err(LandRegistryError::ZoningViolation)
+        } else {
+            Ok(())
+        }
+    })
+}
+
+fn validate_district_definition(
+    definition: &DistrictDefinition,
+    exclude: Option<DistrictId>,
+) -> LandRegistryResult<()> {
+    if definition.name.trim().is_empty() || definition.zoning.allowed_land_types.is_empty() {
         return Err(LandRegistryError::InvalidInput);
     }
     
-    // Check for overlapping land
-    if check_land_overlap(&registration.coordinates, &registration.dimensions) {
-        return Err(LandRegistryError::LandAlreadyExists);
+    let (min, max) = (&definition.min_coords, &definition.max_coords);
+    if !validate_coordinates(min) || !validate_coordinates(max) ||
+       min.x >= max.x || min.y >= max.y || min.z >= max.z {
+        return Err(LandRegistryError::InvalidCoordinates);
     }
     
-    let land_id = NEXT_LAND_ID.with(|id| {
-        let current_id = *id.borrow();
-        *id.borrow_mut() = current_id + 1;
-        current_id
-    });
-    
-    let now = time();
-    let land_info = LandInfo {
-        id: land_id,
-        owner: caller,
-        coordinates: registration.coordinates.clone(),
-        dimensions: registration.dimensions.clone(),
-        land_type: registration.land_type,
-        description: registration.description,
-        metadata: registration.metadata,
-        created_at: now,
-        last_updated: now,
+    // Districts may not overlap, so every point has at most one zoning authority
+    let dims = Dimensions {
+        width: (max.x - min.x) as u32,
+        height: (max.y - min.y) as u32,
+        depth: (max.z - min.z) as u32,
     };
-    
-    // Store land
-    LAND_STORAGE.with(|storage| {
-        storage.borrow_mut().insert(land_id, land_info.clone());
-    });
-    
-    // Update ownership index
-    OWNERSHIP_INDEX.with(|index| {
-        index.borrow_mut()
-            .entry(caller)
-            .or_insert_with(HashSet::new)
-            .insert(land_id);
-    });
-    
-    // Update coordinate index
-    COORDINATE_INDEX.with(|index| {
-        index.borrow_mut().insert(
-            (registration.coordinates.x, registration.coordinates.y, registration.coordinates.z),
-            land_id,
-        );
+    let overlaps = DISTRICTS.with(|districts| {
+        districts.borrow().values()
+            .filter(|district| Some(district.id) != exclude)
+            .any(|district| district_intersects(district, min, &dims))
     });
+    if overlaps {
+        return Err(LandRegistryError::InvalidCoordinates);
+    }
     
-    // Record transaction
-    let transaction = TransactionRecord {
-        land_id,
-        from: Principal::anonymous(),
-        to: caller,
-        price: None,
-        transaction_type: TransactionType::Registration,
-        timestamp: now,
+    Ok(())
+}
+
+// Attribute values must match an admin-defined schema; keys may appear once per parcel
+fn validate_attributes(metadata: &Option<LandMetadata>) -> LandRegistryResult<()> {
+    ATTRIBUTE_SCHEMAS.with(|schemas| attributes_conform(metadata, &schemas.borrow()))
+}
+
+fn attributes_conform(
+    metadata: &Option<LandMetadata>,
+    schemas: &HashMap<String, AttributeSchema>,
+) -> LandRegistryResult<()> {
+    let Some(attributes) = metadata.as_ref().and_then(|m| m.attributes.as_ref()) else {
+        return Ok(());
     };
     
-    TRANSACTION_HISTORY.with(|history| {
-        history.borrow_mut().push(transaction);
-    });
-    
-    Ok(land_id)
+    let mut seen = HashSet::new();
+    for attribute in attributes {
+        if !seen.insert(attribute.key.as_str()) {
+            return Err(LandRegistryError::InvalidAttribute);
+        }
+        
+        let schema = schemas.get(&attribute.key).ok_or(LandRegistryError::InvalidAttribute)?;
+        let valid = match (&schema.kind, &attribute.value) {
+            (AttributeKind::Enum(options), AttributeValue::Enum(value)) => options.contains(value),
+            (AttributeKind::Number { min, max }, AttributeValue::Number(value)) => {
+                min.map(|min| *value >= min).unwrap_or(true) &&
+                max.map(|max| *value <= max).unwrap_or(true)
+            }
+            (AttributeKind::Bool, AttributeValue::Bool(_)) => true,
+            (AttributeKind::Text { max_length }, AttributeValue::Text(value)) => {
+                max_length.map(|max| value.chars().count() <= max as usize).unwrap_or(true)
+            }
+            _ => false,
+        };
+        if !valid {
+            return Err(LandRegistryError::InvalidAttribute);
+        }
+    }
+    Ok(())
 }
 
-#[query]
-fn get_land(land_id: LandId) -> Option<LandInfo> {
-    LAND_STORAGE.with(|storage| {
-        storage.borrow().get(&land_id).cloned()
-    })
+fn attribute_display(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::Enum(value) | AttributeValue::Text(value) => value.clone(),
+        AttributeValue::Number(value) => value.to_string(),
+        AttributeValue::Bool(value) => value.to_string(),
+    }
 }
 
-#[query]
-fn get_all_lands() -> Vec<LandInfo> {
-    LAND_STORAGE.with(|storage| {
-        storage.borrow().values().cloned().collect()
-    })
+fn land_attributes(land: &LandInfo) -> &[LandAttribute] {
+    land.metadata.as_ref()
+        .and_then(|m| m.attributes.as_deref())
+        .unwrap_or(&[])
 }
 
-#[query]
-fn get_land_owner(land_id: LandId) -> Option<Principal> {
-    LAND_STORAGE.with(|storage| {
-        storage.borrow().get(&land_id).map(|land| land.owner)
+// Listing views
+//
+// A listing's version fingerprints everything a buyer sees: the current land state,
+// the seller and the price. Any edit to the parcel or the listing changes it.
+fn listing_version(land: &LandInfo, listing: &ListingRecord) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(land).unwrap_or_default());
+    hasher.update(listing.seller.as_slice());
+    hasher.update(listing.price.to_be_bytes());
+    hasher.update(listing.listed_at.to_be_bytes());
+    let digest = hasher.finalize();
+    u64::from_be_bytes(digest[..8].try_into().unwrap_or_default())
+}
+
+fn join_listing(listing: &ListingRecord) -> Option<MarketplaceListing> {
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&listing.land_id).cloned()
+    })?;
+    
+    Some(MarketplaceListing {
+        land_id: listing.land_id,
+        seller: listing.seller,
+        price: listing.price,
+        listed_at: listing.listed_at,
+        version: listing_version(&land_info, listing),
+        land_info,
     })
 }
 
-#[query]
-fn get_lands_by_owner(owner: Principal) -> Vec<LandInfo> {
-    OWNERSHIP_INDEX.with(|index| {
-        let land_ids = index.borrow().get(&owner).cloned().unwrap_or_default();
-        LAND_STORAGE.with(|storage| {
-            let storage = storage.borrow();
-            land_ids.iter()
-                .filter_map(|&id| storage.get(&id).cloned())
-                .collect()
-        })
+fn join_listings<'a>(listings: impl Iterator<Item = &'a ListingRecord>) -> Vec<MarketplaceListing> {
+    listings.filter_map(join_listing).collect()
+}
+
+// Secondary index maintenance
+fn index_land_type(land: &LandInfo) {
+    SECONDARY_INDEXES.with(|indexes| {
+        indexes.borrow_mut().by_type
+            .entry(land.land_type.clone())
+            .or_default()
+            .insert(land.id);
+    });
+}
+
+fn unindex_land_type(land_id: LandId) {
+    SECONDARY_INDEXES.with(|indexes| {
+        let mut indexes = indexes.borrow_mut();
+        for lands in indexes.by_type.values_mut() {
+            lands.remove(&land_id);
+        }
+        indexes.by_type.retain(|_, lands| !lands.is_empty());
+    });
+}
+
+fn current_land_type(land_id: LandId) -> Option<LandType> {
+    LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).map(|land| land.land_type.clone())
     })
 }
 
-#[update]
-fn transfer_land(land_id: LandId, new_owner: Principal) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
+// All marketplace writes go through insert_listing/remove_listing so the indexes stay in step
+fn insert_listing(listing: ListingRecord) {
+    remove_listing(listing.land_id);
     
-    // Check if land exists and caller is owner
-    let land_info = LAND_STORAGE.with(|storage| {
-        storage.borrow().get(&land_id).cloned()
-    }).ok_or(LandRegistryError::LandNotFound)?;
+    SECONDARY_INDEXES.with(|indexes| {
+        let mut indexes = indexes.borrow_mut();
+        if let Some(land_type) = current_land_type(listing.land_id) {
+            indexes.for_sale_by_type.entry(land_type).or_default().insert(listing.land_id);
+        }
+        indexes.by_price.insert((listing.price, listing.land_id));
+        indexes.by_listed_at.insert((listing.listed_at, listing.land_id));
+    });
     
-    if land_info.owner != caller {
-        return Err(LandRegistryError::Unauthorized);
-    }
+    MARKETPLACE.with(|marketplace| {
+        marketplace.borrow_mut().insert(listing.land_id, listing);
+    });
+}
+
+fn remove_listing(land_id: LandId) -> Option<ListingRecord> {
+    let removed = MARKETPLACE.with(|marketplace| {
+        marketplace.borrow_mut().remove(&land_id)
+    });
     
-    if new_owner == caller {
-        return Err(LandRegistryError::InvalidInput);
+    if let Some(ref listing) = removed {
+        SECONDARY_INDEXES.with(|indexes| {
+            let mut indexes = indexes.borrow_mut();
+            for lands in indexes.for_sale_by_type.values_mut() {
+                lands.remove(&land_id);
+            }
+            indexes.for_sale_by_type.retain(|_, lands| !lands.is_empty());
+            indexes.by_price.remove(&(listing.price, land_id));
+            indexes.by_listed_at.remove(&(listing.listed_at, land_id));
+        });
     }
     
-    // Remove from marketplace if listed
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow_mut().remove(&land_id);
+    removed
+}
+
+// Parcels enter, leave and change hands only through insert_land/remove_land_record/set_land_owner,
+// which keep storage and every land index in step
+fn insert_land(land: LandInfo) {
+    index_land_spatially(&land);
+    index_land_text(&land);
+    index_land_type(&land);
+    
+    OWNERSHIP_INDEX.with(|index| {
+        index.borrow_mut()
+            .entry(land.owner)
+            .or_insert_with(HashSet::new)
+            .insert(land.id);
+    });
+    
+    COORDINATE_INDEX.with(|index| {
+        index.borrow_mut().insert(
+            (land.coordinates.x, land.coordinates.y, land.coordinates.z),
+            land.id,
+        );
     });
     
-    // Update ownership in land storage
     LAND_STORAGE.with(|storage| {
-        if let Some(land) = storage.borrow_mut().get_mut(&land_id) {
-            land.owner = new_owner;
-            land.last_updated = time();
+        storage.borrow_mut().insert(land.id, land);
+    });
+}
+
+fn remove_land_record(land_id: LandId) -> Option<LandInfo> {
+    let land = LAND_STORAGE.with(|storage| {
+        storage.borrow_mut().remove(&land_id)
+    })?;
+    
+    unindex_land_spatially(&land);
+    unindex_land_text(&land);
+    unindex_land_type(land.id);
+    
+    COORDINATE_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        let key = (land.coordinates.x, land.coordinates.y, land.coordinates.z);
+        if index.get(&key) == Some(&land.id) {
+            index.remove(&key);
         }
     });
     
-    // Update ownership index
+    remove_listing(land_id);
+    clear_offers(land_id);
+    
     OWNERSHIP_INDEX.with(|index| {
         let mut index = index.borrow_mut();
-        
-        // Remove from current owner
-        if let Some(lands) = index.get_mut(&caller) {
+        if let Some(lands) = index.get_mut(&land.owner) {
             lands.remove(&land_id);
             if lands.is_empty() {
-                index.remove(&caller);
+                index.remove(&land.owner);
             }
         }
-        
-        // Add to new owner
-        index.entry(new_owner)
-            .or_insert_with(HashSet::new)
-            .insert(land_id);
-    });
-    
-    // Record transaction
-    let transaction = TransactionRecord {
-        land_id,
-        from: caller,
-        to: new_owner,
-        price: None,
-        transaction_type: TransactionType::Transfer,
-        timestamp: time(),
-    };
-    
-    TRANSACTION_HISTORY.with(|history| {
-        history.borrow_mut().push(transaction);
     });
     
-    Ok(())
+    Some(land)
 }
 
-#[update]
-fn list_for_sale(land_id: LandId, price: Price) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
-    
-    let land_info = LAND_STORAGE.with(|storage| {
-        storage.borrow().get(&land_id).cloned()
-    }).ok_or(LandRegistryError::LandNotFound)?;
-    
-    if land_info.owner != caller {
-        return Err(LandRegistryError::Unauthorized);
-    }
-    
-    if price == 0 {
-        return Err(LandRegistryError::InvalidInput);
-    }
+// Returns the parcel before and after; any listing and offers are withdrawn
+fn set_land_owner(land_id: LandId, new_owner: Principal, now: Timestamp) -> Option<(LandInfo, LandInfo)> {
+    let (previous, updated) = LAND_STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let land = storage.get_mut(&land_id)?;
+        let previous = land.clone();
+        land.owner = new_owner;
+        land.last_updated = now;
+        Some((previous, land.clone()))
+    })?;
     
-    let listing = MarketplaceListing {
-        land_id,
-        seller: caller,
-        price,
-        listed_at: time(),
-        land_info,
-    };
+    remove_listing(land_id);
+    clear_offers(land_id);
     
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow_mut().insert(land_id, listing);
+    OWNERSHIP_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        if let Some(lands) = index.get_mut(&previous.owner) {
+            lands.remove(&land_id);
+            if lands.is_empty() {
+                index.remove(&previous.owner);
+            }
+        }
+        index.entry(new_owner)
+            .or_insert_with(HashSet::new)
+            .insert(land_id);
     });
     
-    Ok(())
+    Some((previous, updated))
 }
 
-#[update]
-fn remove_from_sale(land_id: LandId) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
-    
-    let listing = MARKETPLACE.with(|marketplace| {
-        marketplace.borrow().get(&land_id).cloned()
-    }).ok_or(LandRegistryError::LandNotForSale)?;
+fn build_secondary_indexes() -> SecondaryIndexes {
+    let mut indexes = SecondaryIndexes::default();
+    LAND_STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        for land in storage.values() {
+            indexes.by_type.entry(land.land_type.clone()).or_default().insert(land.id);
+        }
+        
+        MARKETPLACE.with(|marketplace| {
+            for listing in marketplace.borrow().values() {
+                if let Some(land) = storage.get(&listing.land_id) {
+                    indexes.for_sale_by_type.entry(land.land_type.clone()).or_default().insert(listing.land_id);
+                }
+                indexes.by_price.insert((listing.price, listing.land_id));
+                indexes.by_listed_at.insert((listing.listed_at, listing.land_id));
+            }
+        });
+    });
+    indexes
+}
+
+fn rebuild_secondary_indexes() {
+    let rebuilt = build_secondary_indexes();
+    SECONDARY_INDEXES.with(|indexes| *indexes.borrow_mut() = rebuilt);
+}
+
+// Differences between the maintained indexes and ones rebuilt from primary storage
+fn secondary_index_issues() -> Vec<String> {
+    let expected = build_secondary_indexes();
+    SECONDARY_INDEXES.with(|indexes| {
+        let actual = indexes.borrow();
+        let mut issues = Vec::new();
+        
+        for land_type in ALL_LAND_TYPES.iter() {
+            let name = land_type_name(land_type);
+            let empty = BTreeSet::new();
+            if expected.by_type.get(land_type).unwrap_or(&empty) != actual.by_type.get(land_type).unwrap_or(&empty) {
+                issues.push(format!("type index for {} does not match storage", name));
+            }
+            if expected.for_sale_by_type.get(land_type).unwrap_or(&empty) !=
+               actual.for_sale_by_type.get(land_type).unwrap_or(&empty) {
+                issues.push(format!("for-sale type index for {} does not match marketplace", name));
+            }
+        }
+        if expected.by_price != actual.by_price {
+            issues.push("price index does not match marketplace".to_string());
+        }
+        if expected.by_listed_at != actual.by_listed_at {
+            issues.push("listed_at index does not match marketplace".to_string());
+        }
+        issues
+    })
+}
+
+// Full-text index
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|token| token.to_lowercase())
+        .filter(|token| token.chars().count() > 1 && !TEXT_STOPWORDS.contains(&token.as_str()))
+        .collect()
+}
+
+// Terms of a land with field weights: features count most, free-text description least
+fn land_terms(land: &LandInfo) -> HashMap<String, u32> {
+    let mut terms = HashMap::new();
+    let mut add = |text: &str, weight: u32| {
+        for token in tokenize(text) {
+            *terms.entry(token).or_insert(0) += weight;
+        }
+    };
     
-    if listing.seller != caller {
-        return Err(LandRegistryError::Unauthorized);
+    add(&land.description, 1);
+    if let Some(ref metadata) = land.metadata {
+        if let Some(ref environment) = metadata.environment {
+            add(environment, 2);
+        }
+        for feature in &metadata.special_features {
+            add(feature, 3);
+        }
+        for utility in &metadata.utilities {
+            add(utility, 2);
+        }
+        for road in &metadata.access_roads {
+            add(road, 2);
+        }
+    }
+    for attribute in land_attributes(land) {
+        if let AttributeValue::Enum(ref value) | AttributeValue::Text(ref value) = attribute.value {
+            add(value, 2);
+        }
     }
+    terms
+}
+
+fn index_land_text(land: &LandInfo) {
+    TEXT_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        for (term, weight) in land_terms(land) {
+            index.entry(term).or_default().insert(land.id, weight);
+        }
+    });
+}
+
+fn unindex_land_text(land: &LandInfo) {
+    TEXT_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        for term in land_terms(land).keys() {
+            if let Some(postings) = index.get_mut(term) {
+                postings.remove(&land.id);
+                if postings.is_empty() {
+                    index.remove(term);
+                }
+            }
+        }
+    });
+}
+
+// TF-IDF over the query terms, scaled to an integer so it can drive the relevance sort
+fn text_scores(query: &str) -> HashMap<LandId, u64> {
+    let total_lands = LAND_STORAGE.with(|storage| storage.borrow().len()) as f64;
+    let mut scores: HashMap<LandId, f64> = HashMap::new();
     
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow_mut().remove(&land_id);
+    TEXT_INDEX.with(|index| {
+        let index = index.borrow();
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+        for term in terms {
+            if let Some(postings) = index.get(&term) {
+                let idf = (1.0 + total_lands / postings.len() as f64).ln();
+                for (&land_id, &weight) in postings {
+                    *scores.entry(land_id).or_insert(0.0) += weight as f64 * idf;
+                }
+            }
+        }
     });
     
-    Ok(())
+    scores.into_iter()
+        .map(|(land_id, score)| (land_id, (score * 1000.0) as u64))
+        .collect()
+}
+
+// Pagination
+//
+// Pages are keyset-based: items are ordered by (sort value, id) and the cursor holds
+// the key of the last item returned, so inserts and removals never shift later pages.
+fn page_limit(page: &PageRequest) -> usize {
+    page.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT) as usize
+}
+
+// Only the items past the cursor are considered, and only the first `limit` of those are
+// put in order, so a page never sorts the whole collection
+fn paginate<T>(keyed: Vec<(u64, u64, T)>, page: &PageRequest) -> Page<T> {
+    let descending = match page.direction {
+        Some(ref direction) => *direction == SortDirection::Descending,
+        None => matches!(page.sort_by, Some(SortKey::Relevance)),
+    };
+    let order = |a: &(u64, u64, T), b: &(u64, u64, T)| {
+        let ordering = (a.0, a.1).cmp(&(b.0, b.1));
+        if descending { ordering.reverse() } else { ordering }
+    };
+    
+    let total_count = keyed.len() as u64;
+    let limit = page_limit(page);
+    let mut remaining: Vec<(u64, u64, T)> = match &page.cursor {
+        Some(cursor) => {
+            let after = (cursor.sort_value, cursor.id);
+            keyed.into_iter()
+                .filter(|(sort_value, id, _)| {
+                    if descending {
+                        (*sort_value, *id) < after
+                    } else {
+                        (*sort_value, *id) > after
+                    }
+                })
+                .collect()
+        }
+        None => keyed,
+    };
+    
+    let has_more = remaining.len() > limit;
+    if has_more {
+        remaining.select_nth_unstable_by(limit, order);
+        remaining.truncate(limit);
+    }
+    remaining.sort_by(order);
+    
+    let next_cursor = match remaining.last() {
+        Some((sort_value, id, _)) if has_more => Some(PageCursor { sort_value: *sort_value, id: *id }),
+        _ => None,
+    };
+    
+    Page {
+        items: remaining.into_iter().map(|(_, _, item)| item).collect(),
+        next_cursor,
+        total_count,
+    }
+}
+
+// Takes a page from `items`, which already starts after the cursor in the requested order
+fn page_from_iter<T>(total_count: u64, mut items: impl Iterator<Item = (u64, T)>, page: &PageRequest) -> Page<T> {
+    let page_items: Vec<(u64, T)> = items.by_ref().take(page_limit(page)).collect();
+    let next_cursor = match (items.next(), page_items.last()) {
+        (Some(_), Some((id, _))) => Some(PageCursor { sort_value: *id, id: *id }),
+        _ => None,
+    };
+    
+    Page {
+        items: page_items.into_iter().map(|(_, item)| item).collect(),
+        next_cursor,
+        total_count,
+    }
+}
+
+// Stores keyed by id are paged in id order by walking the map from the cursor, so only the
+// returned items are cloned; `sort_by` is ignored
+fn id_page<V, T>(
+    store: &BTreeMap<u64, V>,
+    page: &PageRequest,
+    include: impl Fn(&V) -> bool,
+    project: impl Fn(&V) -> T,
+) -> Page<T> {
+    let total_count = store.values().filter(|value| include(value)).count() as u64;
+    let descending = page.direction == Some(SortDirection::Descending);
+    let entries: Box<dyn Iterator<Item = (&u64, &V)>> = match (page.cursor.as_ref().map(|cursor| cursor.id), descending) {
+        (None, false) => Box::new(store.iter()),
+        (None, true) => Box::new(store.iter().rev()),
+        (Some(after), false) => Box::new(store.range((Bound::Excluded(after), Bound::Unbounded))),
+        (Some(after), true) => Box::new(store.range(..after).rev()),
+    };
+    let items = entries
+        .filter(|(_, value)| include(value))
+        .map(|(id, value)| (*id, project(value)));
+    page_from_iter(total_count, items, page)
+}
+
+// The same for append-only logs, where an item's id is its position
+fn position_page<V, T>(
+    log: &[V],
+    page: &PageRequest,
+    include: impl Fn(&V) -> bool,
+    project: impl Fn(&V) -> T,
+) -> Page<T> {
+    let total_count = log.iter().filter(|value| include(value)).count() as u64;
+    let descending = page.direction == Some(SortDirection::Descending);
+    let entries: Box<dyn Iterator<Item = (usize, &V)>> = match (page.cursor.as_ref().map(|cursor| cursor.id as usize), descending) {
+        (None, false) => Box::new(log.iter().enumerate()),
+        (None, true) => Box::new(log.iter().enumerate().rev()),
+        (Some(after), false) => Box::new(log.iter().enumerate().skip(after.saturating_add(1))),
+        (Some(after), true) => Box::new(log.iter().enumerate().take(after).rev()),
+    };
+    let items = entries
+        .filter(|(_, value)| include(value))
+        .map(|(index, value)| (index as u64, project(value)));
+    page_from_iter(total_count, items, page)
 }
 
+fn land_page(lands: Vec<LandInfo>, page: &PageRequest) -> Page<LandInfo> {
+    scored_land_page(lands.into_iter().map(|land| (land, 0)).collect(), page)
+}
+
+// Parcels that are not listed sort as if priced and listed at 0
+fn scored_land_page(lands: Vec<(LandInfo, u64)>, page: &PageRequest) -> Page<LandInfo> {
+    let sort_by = page.sort_by.clone().unwrap_or(SortKey::Id);
+    let keyed = MARKETPLACE.with(|marketplace| {
+        let marketplace = marketplace.borrow();
+        lands.into_iter()
+            .map(|(land, score)| {
+                let listing = marketplace.get(&land.id);
+                let sort_value = match sort_by {
+                    SortKey::Id => land.id,
+                    SortKey::Price => listing.map(|l| l.price).unwrap_or(0),
+                    SortKey::Area => calculate_land_area(&land.dimensions) as u64,
+                    SortKey::ListedAt => listing.map(|l| l.listed_at).unwrap_or(0),
+                    SortKey::CreatedAt => land.created_at,
+                    SortKey::Relevance => score,
+                };
+                (sort_value, land.id, land)
+            })
+            .collect()
+    });
+    paginate(keyed, page)
+}
+
+fn listing_page(listings: Vec<MarketplaceListing>, page: &PageRequest) -> Page<MarketplaceListing> {
+    scored_listing_page(listings.into_iter().map(|listing| (listing, 0)).collect(), page)
+}
+
+fn scored_listing_page(listings: Vec<(MarketplaceListing, u64)>, page: &PageRequest) -> Page<MarketplaceListing> {
+    let sort_by = page.sort_by.clone().unwrap_or(SortKey::Id);
+    let keyed = listings.into_iter()
+        .map(|(listing, score)| {
+            let sort_value = match sort_by {
+                SortKey::Id => listing.land_id,
+                SortKey::Price => listing.price,
+                SortKey::Area => calculate_land_area(&listing.land_info.dimensions) as u64,
+                SortKey::ListedAt => listing.listed_at,
+                SortKey::CreatedAt => listing.land_info.created_at,
+                SortKey::Relevance => score,
+            };
+            (sort_value, listing.land_id, listing)
+        })
+        .collect();
+    paginate(keyed, page)
+}
+
+// Transactions are keyed by their position in the append-only history
+fn transaction_page(transactions: Vec<(u64, TransactionRecord)>, page: &PageRequest) -> Page<TransactionRecord> {
+    let sort_by = page.sort_by.clone().unwrap_or(SortKey::Id);
+    let keyed = transactions.into_iter()
+        .map(|(index, tx)| {
+            let sort_value = match sort_by {
+                SortKey::Id => index,
+                SortKey::Price => tx.price.unwrap_or(0),
+                SortKey::Area | SortKey::Relevance => 0,
+                SortKey::ListedAt | SortKey::CreatedAt => tx.timestamp,
+            };
+            (sort_value, index, tx)
+        })
+        .collect();
+    paginate(keyed, page)
+}
+
+// Public functions
+
 #[update]
-fn buy_land(land_id: LandId) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
+fn register_land(registration: LandRegistration) -> LandRegistryResult<LandId> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Registration)?;
     
-    let listing = MARKETPLACE.with(|marketplace| {
-        marketplace.borrow().get(&land_id).cloned()
-    }).ok_or(LandRegistryError::LandNotForSale)?;
+    // Validate input
+    if !validate_coordinates(&registration.coordinates) {
+        return Err(LandRegistryError::InvalidCoordinates);
+    }
+    
+    if !validate_dimensions(&registration.dimensions) {
+        return Err(LandRegistryError::InvalidDimensions);
+    }
     
-    if listing.seller == caller {
+    if registration.description.trim().is_empty() {
         return Err(LandRegistryError::InvalidInput);
     }
     
-    // In a real implementation, you'd handle payment here
-    // For now, we'll simulate the purchase
+    // Check for overlapping land
+    if check_land_overlap(&registration.coordinates, &registration.dimensions) {
+        return Err(LandRegistryError::LandAlreadyExists);
+    }
     
-    // Transfer ownership
-    LAND_STORAGE.with(|storage| {
-        if let Some(land) = storage.borrow_mut().get_mut(&land_id) {
-            land.owner = caller;
-            land.last_updated = time();
-        }
-    });
+    check_zoning(&registration.coordinates, &registration.dimensions, &registration.land_type)?;
     
-    // Update ownership index
-    OWNERSHIP_INDEX.with(|index| {
-        let mut index = index.borrow_mut();
-        
-        // Remove from seller
-        if let Some(lands) = index.get_mut(&listing.seller) {
-            lands.remove(&land_id);
-            if lands.is_empty() {
-                index.remove(&listing.seller);
-            }
-        }
-        
-        // Add to buyer
-        index.entry(caller)
-            .or_insert_with(HashSet::new)
-            .insert(land_id);
-    });
+    validate_attributes(&registration.metadata)?;
     
-    // Remove from marketplace
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow_mut().remove(&land_id);
+    let land_id = NEXT_LAND_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
     });
     
+    let now = time();
+    let land_info = LandInfo {
+        id: land_id,
+        owner: caller,
+        coordinates: registration.coordinates.clone(),
+        dimensions: registration.dimensions.clone(),
+        land_type: registration.land_type,
+        description: registration.description,
+        metadata: registration.metadata,
+        created_at: now,
+        last_updated: now,
+        holds: None,
+    };
+    
+    // Store land
+    insert_land(land_info.clone());
+    
     // Record transaction
     let transaction = TransactionRecord {
         land_id,
-        from: listing.seller,
+        from: Principal::anonymous(),
         to: caller,
-        price: Some(listing.price),
-        transaction_type: TransactionType::Sale,
-        timestamp: time(),
+        price: None,
+        transaction_type: TransactionType::Registration,
+        timestamp: now,
     };
     
     TRANSACTION_HISTORY.with(|history| {
         history.borrow_mut().push(transaction);
     });
     
-    Ok(())
+    record_audit(caller, AuditAction::LandRegistered, AuditTarget::Land(land_id), None, audit_json(&land_info));
+    
+    certify_land(land_id);
+    
+    Ok(land_id)
 }
 
 #[query]
-fn get_marketplace_listings() -> Vec<MarketplaceListing> {
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow().values().cloned().collect()
+fn get_land(land_id: LandId) -> Option<LandInfo> {
+    LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
     })
 }
 
 #[query]
-fn get_lands_for_sale_by_type(land_type: LandType) -> Vec<MarketplaceListing> {
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow().values()
-            .filter(|listing| {
-                matches!(listing.land_info.land_type, ref t if std::mem::discriminant(t) == std::mem::discriminant(&land_type))
-            })
-            .cloned()
-            .collect()
-    })
+fn get_all_lands(page: PageRequest) -> Page<LandInfo> {
+    let lands: Vec<LandInfo> = LAND_STORAGE.with(|storage| {
+        storage.borrow().values().cloned().collect()
+    });
+    
+    land_page(lands, &page)
 }
 
 #[query]
-fn search_lands(filters: SearchFilters) -> Vec<LandInfo> {
+fn get_land_owner(land_id: LandId) -> Option<Principal> {
     LAND_STORAGE.with(|storage| {
-        storage.borrow().values()
-            .filter(|land| {
-                // Filter by land type
-                if let Some(ref filter_type) = filters.land_type {
-                    if std::mem::discriminant(&land.land_type) != std::mem::discriminant(filter_type) {
-                        return false;
-                    }
-                }
-                
-                // Filter by area
-                if let Some(min_area) = filters.min_area {
-                    if calculate_land_area(&land.dimensions) < min_area {
-                        return false;
-                    }
-                }
-                
-                // Filter by coordinates range
-                if let Some((min_coords, max_coords)) = &filters.coordinates_range {
-                    if land.coordinates.x < min_coords.x || land.coordinates.x > max_coords.x ||
-                       land.coordinates.y < min_coords.y || land.coordinates.y > max_coords.y ||
-                       land.coordinates.z < min_coords.z || land.coordinates.z > max_coords.z {
-                        return false;
-                    }
-                }
-                
-                // Filter by features
-                if let Some(ref required_features) = filters.features {
-                    if let Some(ref metadata) = land.metadata {
-                        for feature in required_features {
-                            if !metadata.special_features.iter().any(|f| f.contains(feature)) {
-                                return false;
-                            }
-                        }
-                    } else if !required_features.is_empty() {
-                        return false;
-                    }
-                }
-                
-                true
-            })
-            .cloned()
-            .collect()
+        storage.borrow().get(&land_id).map(|land| land.owner)
     })
 }
 
 #[query]
-fn search_by_coordinates(min_coords: Coordinates, max_coords: Coordinates) -> Vec<LandInfo> {
-    LAND_STORAGE.with(|storage| {
-        storage.borrow().values()
-            .filter(|land| {
-                land.coordinates.x >= min_coords.x && land.coordinates.x <= max_coords.x &&
-                land.coordinates.y >= min_coords.y && land.coordinates.y <= max_coords.y &&
-                land.coordinates.z >= min_coords.z && land.coordinates.z <= max_coords.z
-            })
-            .cloned()
-            .collect()
-    })
+fn get_lands_by_owner(owner: Principal, page: PageRequest) -> Page<LandInfo> {
+    let lands: Vec<LandInfo> = OWNERSHIP_INDEX.with(|index| {
+        let land_ids = index.borrow().get(&owner).cloned().unwrap_or_default();
+        LAND_STORAGE.with(|storage| {
+            let storage = storage.borrow();
+            land_ids.iter()
+                .filter_map(|&id| storage.get(&id).cloned())
+                .collect()
+        })
+    });
+    
+    land_page(lands, &page)
 }
 
-#[query]
-fn get_transaction_history(land_id: Option<LandId>) -> Vec<TransactionRecord> {
+#[update]
+fn transfer_land(land_id: LandId, new_owner: Principal) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Transfers)?;
+    
+    // Check if land exists and caller is owner
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    if land_info.owner != caller {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    ensure_not_held(&land_info)?;
+    
+    if new_owner == caller {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    // Update ownership; any listing is withdrawn
+    let updated = set_land_owner(land_id, new_owner, time()).map(|(_, updated)| updated);
+    record_audit(caller, AuditAction::LandTransferred, AuditTarget::Land(land_id), audit_json(&land_info), audit_json(&updated));
+    clear_operators(land_id);
+    cancel_pending_rezoning(land_id, caller, "Parcel changed hands");
+    
+    // Record transaction
+    let transaction = TransactionRecord {
+        land_id,
+        from: caller,
+        to: new_owner,
+        price: None,
+        transaction_type: TransactionType::Transfer,
+        timestamp: time(),
+    };
+    
     TRANSACTION_HISTORY.with(|history| {
-        let history = history.borrow();
-        match land_id {
-            Some(id) => history.iter()
-                .filter(|tx| tx.land_id == id)
-                .cloned()
-                .collect(),
-            None => history.clone(),
-        }
-    })
+        history.borrow_mut().push(transaction);
+    });
+    
+    certify_land(land_id);
+    
+    Ok(())
 }
 
-#[query]
-fn get_price_history(land_id: LandId) -> Vec<(Timestamp, Price)> {
-    TRANSACTION_HISTORY.with(|history| {
-        history.borrow().iter()
-            .filter(|tx| tx.land_id == land_id && tx.price.is_some())
-            .map(|tx| (tx.timestamp, tx.price.unwrap()))
-            .collect()
-    })
-}
-
-#[query]
-fn get_land_statistics() -> LandStatistics {
-    let total_lands = LAND_STORAGE.with(|storage| storage.borrow().len() as u64);
-    let total_owners = OWNERSHIP_INDEX.with(|index| index.borrow().len() as u64);
-    let lands_for_sale = MARKETPLACE.with(|marketplace| marketplace.borrow().len() as u64);
-    let total_transactions = TRANSACTION_HISTORY.with(|history| history.borrow().len() as u64);
+#[update]
+fn list_for_sale(land_id: LandId, price: Price) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Marketplace)?;
     
-    let average_price = if lands_for_sale > 0 {
-        MARKETPLACE.with(|marketplace| {
-            let total: u64 = marketplace.borrow().values().map(|listing| listing.price).sum();
-            Some(total / lands_for_sale)
-        })
-    } else {
-        None
-    };
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
     
-    LandStatistics {
-        total_lands,
-        total_owners,
-        lands_for_sale,
-        average_price,
-        total_transactions,
+    if !acts_for_owner(&land_info, &caller, OperatorPermission::ManageListing) {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    ensure_not_held(&land_info)?;
+    
+    if price == 0 {
+        return Err(LandRegistryError::InvalidInput);
     }
+    
+    // An operator lists on the owner's behalf; the owner remains the seller
+    let listing = ListingRecord {
+        land_id,
+        seller: land_info.owner,
+        listed_by: caller,
+        price,
+        listed_at: time(),
+    };
+    
+    let previous = MARKETPLACE.with(|marketplace| marketplace.borrow().get(&land_id).cloned());
+    record_audit(caller, AuditAction::LandListed, AuditTarget::Land(land_id), audit_json(&previous), audit_json(&listing));
+    
+    insert_listing(listing);
+    
+    certify_land(land_id);
+    
+    Ok(())
 }
 
-#[query]
-fn get_lands_near_coordinates(coords: Coordinates, radius: u32) -> Vec<LandInfo> {
-    LAND_STORAGE.with(|storage| {
-        storage.borrow().values()
-            .filter(|land| {
-                let dx = (land.coordinates.x - coords.x).abs() as u32;
-                let dy = (land.coordinates.y - coords.y).abs() as u32;
-                let dz = (land.coordinates.z - coords.z).abs() as u32;
-                dx <= radius && dy <= radius && dz <= radius
-            })
-            .cloned()
-            .collect()
-    })
+#[update]
+fn remove_from_sale(land_id: LandId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let listing = MARKETPLACE.with(|marketplace| {
+        marketplace.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotForSale)?;
+    
+    let authorized = listing.seller == caller || LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id)
+            .map(|land| acts_for_owner(land, &caller, OperatorPermission::ManageListing))
+            .unwrap_or(false)
+    });
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    remove_listing(land_id);
+    record_audit(caller, AuditAction::LandDelisted, AuditTarget::Land(land_id), audit_json(&listing), None);
+    
+    certify_land(land_id);
+    
+    Ok(())
 }
 
+// The buyer passes the listing version they were shown; the sale is refused if the
+// land or the listing changed since
 #[update]
-fn update_land_metadata(land_id: LandId, metadata: LandMetadata) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
+fn buy_land(land_id: LandId, expected_version: u64) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Marketplace)?;
+    ensure_not_paused(PauseScope::Transfers)?;
     
-    LAND_STORAGE.with(|storage| {
-        let mut storage = storage.borrow_mut();
-        if let Some(land) = storage.get_mut(&land_id) {
-            if land.owner != caller {
-                return Err(LandRegistryError::Unauthorized);
-            }
-            land.metadata = Some(metadata);
-            land.last_updated = time();
-            Ok(())
-        } else {
-            Err(LandRegistryError::LandNotFound)
-        }
-    })
+    let listing = MARKETPLACE.with(|marketplace| {
+        marketplace.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotForSale)?;
+    
+    let current = join_listing(&listing).ok_or(LandRegistryError::LandNotFound)?;
+    
+    // Whoever set the price, or still acts for the owner, could otherwise sell the parcel to themselves
+    if listing.seller == caller || listing.listed_by == caller || is_operator(&current.land_info, &caller) {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    if current.version != expected_version {
+        return Err(LandRegistryError::ListingChanged);
+    }
+    
+    ensure_not_held(&current.land_info)?;
+    
+    // In a real implementation, you'd handle payment here
+    // For now, we'll simulate the purchase
+    
+    // Transfer ownership; this also takes the parcel off the marketplace
+    let updated = set_land_owner(land_id, caller, time()).map(|(_, updated)| updated);
+    record_audit(caller, AuditAction::LandSold, AuditTarget::Land(land_id), audit_json(&current.land_info), audit_json(&updated));
+    clear_operators(land_id);
+    cancel_pending_rezoning(land_id, caller, "Parcel changed hands");
+    
+    // Record transaction
+    let transaction = TransactionRecord {
+        land_id,
+        from: listing.seller,
+        to: caller,
+        price: Some(listing.price),
+        transaction_type: TransactionType::Sale,
+        timestamp: time(),
+    };
+    
+    TRANSACTION_HISTORY.with(|history| {
+        history.borrow_mut().push(transaction);
+    });
+    
+    certify_land(land_id);
+    
+    Ok(())
 }
 
 #[query]
-fn get_marketplace_listing(land_id: LandId) -> Option<MarketplaceListing> {
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow().get(&land_id).cloned()
-    })
+fn get_marketplace_listings(page: PageRequest) -> Page<MarketplaceListing> {
+    let listings: Vec<MarketplaceListing> = MARKETPLACE.with(|marketplace| {
+        join_listings(marketplace.borrow().values())
+    });
+    
+    listing_page(listings, &page)
 }
 
 #[query]
-fn search_marketplace(filters: SearchFilters) -> Vec<MarketplaceListing> {
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow().values()
-            .filter(|listing| {
-                let land = &listing.land_info;
-                
-                // Filter by land type
-                if let Some(ref filter_type) = filters.land_type {
-                    if std::mem::discriminant(&land.land_type) != std::mem::discriminant(filter_type) {
-                        return false;
-                    }
-                }
-                
-                // Filter by price range
-                if let Some(min_price) = filters.min_price {
-                    if listing.price < min_price {
-                        return false;
-                    }
-                }
-                
-                if let Some(max_price) = filters.max_price {
-                    if listing.price > max_price {
-                        return false;
-                    }
-                }
-                
-                // Filter by area
-                if let Some(min_area) = filters.min_area {
-                    if calculate_land_area(&land.dimensions) < min_area {
-                        return false;
-                    }
-                }
-                
-                // Filter by coordinates range
-                if let Some((min_coords, max_coords)) = &filters.coordinates_range {
-                    if land.coordinates.x < min_coords.x || land.coordinates.x > max_coords.x ||
-                       land.coordinates.y < min_coords.y || land.coordinates.y > max_coords.y ||
-                       land.coordinates.z < min_coords.z || land.coordinates.z > max_coords.z {
-                        return false;
-                    }
-                }
-                
-                true
-            })
-            .cloned()
-            .collect()
-    })
+fn get_lands_for_sale_by_type(land_type: LandType, page: PageRequest) -> Page<MarketplaceListing> {
+    let land_ids = SECONDARY_INDEXES.with(|indexes| {
+        indexes.borrow().for_sale_by_type.get(&land_type).cloned().unwrap_or_default()
+    });
+    let listings: Vec<MarketplaceListing> = MARKETPLACE.with(|marketplace| {
+        let marketplace = marketplace.borrow();
+        join_listings(land_ids.iter().filter_map(|id| marketplace.get(id)))
+    });
+    
+    listing_page(listings, &page)
 }
 
-#[query]
-fn get_user_transactions(user: Principal) -> Vec<TransactionRecord> {
-    TRANSACTION_HISTORY.with(|history| {
-        history.borrow().iter()
-            .filter(|tx| tx.from == user || tx.to == user)
-            .cloned()
-            .collect()
+fn listings_by_ids(land_ids: impl Iterator<Item = LandId>) -> Vec<MarketplaceListing> {
+    MARKETPLACE.with(|marketplace| {
+        let marketplace = marketplace.borrow();
+        join_listings(land_ids.filter_map(|id| marketplace.get(&id)))
     })
 }
 
-// Admin functions
-#[update]
-fn add_admin(new_admin: Principal) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
+#[query]
+fn get_listings_by_price_band(min_price: Price, max_price: Price, page: PageRequest) -> Page<MarketplaceListing> {
+    if min_price > max_price {
+        return listing_page(Vec::new(), &page);
+    }
     
-    let is_admin = ADMINS.with(|admins| {
-        admins.borrow().contains(&caller)
+    let land_ids: Vec<LandId> = SECONDARY_INDEXES.with(|indexes| {
+        indexes.borrow().by_price
+            .range((min_price, LandId::MIN)..=(max_price, LandId::MAX))
+            .map(|(_, id)| *id)
+            .collect()
     });
     
-    if !is_admin {
-        return Err(LandRegistryError::Unauthorized);
+    listing_page(listings_by_ids(land_ids.into_iter()), &page)
+}
+
+#[query]
+fn get_listings_by_listed_at(from: Timestamp, to: Timestamp, page: PageRequest) -> Page<MarketplaceListing> {
+    if from > to {
+        return listing_page(Vec::new(), &page);
     }
     
-    ADMINS.with(|admins| {
-        admins.borrow_mut().insert(new_admin);
+    let land_ids: Vec<LandId> = SECONDARY_INDEXES.with(|indexes| {
+        indexes.borrow().by_listed_at
+            .range((from, LandId::MIN)..=(to, LandId::MAX))
+            .map(|(_, id)| *id)
+            .collect()
     });
     
-    Ok(())
+    listing_page(listings_by_ids(land_ids.into_iter()), &page)
 }
 
-#[query]
-fn is_admin(user: Principal) -> bool {
-    ADMINS.with(|admins| {
-        admins.borrow().contains(&user)
-    })
+// Case-insensitive: 2 for an exact value, 1 for a substring, None when nothing matches
+fn term_score(values: &[String], term: &str) -> Option<u64> {
+    let term = term.to_lowercase();
+    values.iter()
+        .filter_map(|value| {
+            let value = value.to_lowercase();
+            if value == term {
+                Some(2)
+            } else if value.contains(&term) {
+                Some(1)
+            } else {
+                None
+            }
+        })
+        .max()
 }
 
-#[update]
-fn remove_land(land_id: LandId) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
+// Every required term must match; the score is the sum of the term scores
+fn required_terms_score(values: &[String], terms: &Option<Vec<String>>) -> Option<u64> {
+    match terms {
+        Some(terms) => terms.iter().try_fold(0, |score, term| {
+            term_score(values, term).map(|term_score| score + term_score)
+        }),
+        None => Some(0),
+    }
+}
+
+// Search engine shared by every filtered query. Returns the relevance score of a
+// matching land, or None when any filter rejects it. Price filters only match listed land.
+fn score_land(land: &LandInfo, listing: Option<&ListingRecord>, filters: &SearchFilters) -> Option<u64> {
+    // Filter by land type
+    if let Some(ref filter_type) = filters.land_type {
+        if std::mem::discriminant(&land.land_type) != std::mem::discriminant(filter_type) {
+            return None;
+        }
+    }
     
-    let is_admin = ADMINS.with(|admins| {
-        admins.borrow().contains(&caller)
-    });
+    // Filter by owner
+    if let Some(owner) = filters.owner {
+        if land.owner != owner {
+            return None;
+        }
+    }
     
-    if !is_admin {
-        return Err(LandRegistryError::Unauthorized);
+    // Filter by sale state and price range
+    let price_filtered = filters.min_price.is_some() || filters.max_price.is_some();
+    if (filters.for_sale_only == Some(true) || price_filtered) && listing.is_none() {
+        return None;
+    }
+    if let Some(listing) = listing {
+        if filters.min_price.map(|min| listing.price < min).unwrap_or(false) ||
+           filters.max_price.map(|max| listing.price > max).unwrap_or(false) {
+            return None;
+        }
     }
     
-    // Remove from all storages
-    LAND_STORAGE.with(|storage| {
-        storage.borrow_mut().remove(&land_id);
-    });
+    // Filter by area
+    let area = calculate_land_area(&land.dimensions);
+    if filters.min_area.map(|min| area < min).unwrap_or(false) ||
+       filters.max_area.map(|max| area > max).unwrap_or(false) {
+        return None;
+    }
     
-    MARKETPLACE.with(|marketplace| {
-        marketplace.borrow_mut().remove(&land_id);
-    });
+    // Filter by creation time
+    if filters.created_after.map(|after| land.created_at < after).unwrap_or(false) ||
+       filters.created_before.map(|before| land.created_at > before).unwrap_or(false) {
+        return None;
+    }
     
-    // Remove from ownership index
-    OWNERSHIP_INDEX.with(|index| {
-        let mut index = index.borrow_mut();
-        for lands in index.values_mut() {
-            lands.remove(&land_id);
+    // Filter by coordinates range
+    if let Some((min_coords, max_coords)) = &filters.coordinates_range {
+        if land.coordinates.x < min_coords.x || land.coordinates.x > max_coords.x ||
+           land.coordinates.y < min_coords.y || land.coordinates.y > max_coords.y ||
+           land.coordinates.z < min_coords.z || land.coordinates.z > max_coords.z {
+            return None;
         }
-        index.retain(|_, lands| !lands.is_empty());
-    });
+    }
     
-    Ok(())
+    // Filter by metadata
+    let empty = Vec::new();
+    let metadata = land.metadata.as_ref();
+    let mut score = 0;
+    
+    if let Some(ref environment) = filters.environment {
+        let values: Vec<String> = metadata.and_then(|m| m.environment.clone()).into_iter().collect();
+        score += term_score(&values, environment)?;
+    }
+    
+    score += required_terms_score(metadata.map(|m| &m.special_features).unwrap_or(&empty), &filters.features)?;
+    score += required_terms_score(metadata.map(|m| &m.utilities).unwrap_or(&empty), &filters.utilities)?;
+    score += required_terms_score(metadata.map(|m| &m.access_roads).unwrap_or(&empty), &filters.access_roads)?;
+    
+    // Filter by structured attributes, which must match exactly
+    if let Some(ref required) = filters.attributes {
+        let attributes = land_attributes(land);
+        let all_match = required.iter().all(|wanted| {
+            attributes.iter().any(|attribute| attribute.key == wanted.key && attribute.value == wanted.value)
+        });
+        if !all_match {
+            return None;
+        }
+    }
+    
+    Some(score)
 }
 
-// Helper query functions
-#[query]
-fn get_total_supply() -> u64 {
-    LAND_STORAGE.with(|storage| storage.borrow().len() as u64)
+// Narrows a search through the secondary indexes; None means every land is a candidate
+fn search_candidates(filters: &SearchFilters) -> Option<BTreeSet<LandId>> {
+    SECONDARY_INDEXES.with(|indexes| {
+        let indexes = indexes.borrow();
+        let mut candidates: Option<BTreeSet<LandId>> = None;
+        let mut narrow = |ids: BTreeSet<LandId>| {
+            candidates = Some(match candidates.take() {
+                Some(current) => current.intersection(&ids).copied().collect(),
+                None => ids,
+            });
+        };
+        
+        if let Some(ref land_type) = filters.land_type {
+            let index = if filters.for_sale_only == Some(true) {
+                &indexes.for_sale_by_type
+            } else {
+                &indexes.by_type
+            };
+            narrow(index.get(land_type).cloned().unwrap_or_default());
+        }
+        
+        if filters.min_price.is_some() || filters.max_price.is_some() {
+            let min = filters.min_price.unwrap_or(0);
+            let max = filters.max_price.unwrap_or(Price::MAX);
+            let ids = if min <= max {
+                indexes.by_price.range((min, LandId::MIN)..=(max, LandId::MAX)).map(|(_, id)| *id).collect()
+            } else {
+                BTreeSet::new()
+            };
+            narrow(ids);
+        } else if filters.for_sale_only == Some(true) {
+            narrow(indexes.by_price.iter().map(|(_, id)| *id).collect());
+        }
+        
+        candidates
+    })
 }
 
-#[query]
-fn get_next_land_id() -> LandId {
-    NEXT_LAND_ID.with(|id| *id.borrow())
+// A text query restricts results to lands with at least one matching term
+fn run_search(filters: &SearchFilters) -> Vec<(LandInfo, u64)> {
+    let text_scores = filters.text.as_ref().map(|query| text_scores(query));
+    let candidates = search_candidates(filters);
+    
+    LAND_STORAGE.with(|storage| {
+        MARKETPLACE.with(|marketplace| {
+            let marketplace = marketplace.borrow();
+            let storage = storage.borrow();
+            let lands: Box<dyn Iterator<Item = &LandInfo>> = match candidates {
+                Some(ref ids) => Box::new(ids.iter().filter_map(|id| storage.get(id))),
+                None => Box::new(storage.values()),
+            };
+            lands
+                .filter_map(|land| {
+                    let text_score = match text_scores {
+                        Some(ref scores) => *scores.get(&land.id)?,
+                        None => 0,
+                    };
+                    score_land(land, marketplace.get(&land.id), filters)
+                        .map(|score| (land.clone(), score + text_score))
+                })
+                .collect()
+        })
+    })
 }
 
 #[query]
-fn verify_land_ownership(land_id: LandId, claimed_owner: Principal) -> bool {
-    LAND_STORAGE.with(|storage| {
-        storage.borrow().get(&land_id)
-            .map(|land| land.owner == claimed_owner)
-            .unwrap_or(false)
-    })
+fn search_lands(filters: SearchFilters, page: PageRequest) -> Page<LandInfo> {
+    scored_land_page(run_search(&filters), &page)
 }
 
-// Backup and restore functions for data migration
+// Ranked full-text search; results come most relevant first unless another sort is requested
 #[query]
-fn backup_lands() -> Vec<LandInfo> {
-    let caller = ic_cdk::caller();
-    let is_admin = ADMINS.with(|admins| admins.borrow().contains(&caller));
-    
-    if is_admin {
-        LAND_STORAGE.with(|storage| {
-            storage.borrow().values().cloned().collect()
-        })
-    } else {
-        Vec::new()
-    }
+fn search_text(query: String, page: PageRequest) -> Page<LandInfo> {
+    let filters = SearchFilters { text: Some(query), ..Default::default() };
+    let page = PageRequest { sort_by: page.sort_by.clone().or(Some(SortKey::Relevance)), ..page };
+    scored_land_page(run_search(&filters), &page)
 }
 
-#[update]
-fn restore_lands(lands: Vec<LandInfo>) -> LandRegistryResult<()> {
-    let caller = ic_cdk::caller();
-    let is_admin = ADMINS.with(|admins| admins.borrow().contains(&caller));
+// Facets count the whole result set by land type and by every enum or bool attribute
+fn facet_counts(lands: &[(LandInfo, u64)]) -> Vec<FacetCount> {
+    let facetable: HashSet<String> = ATTRIBUTE_SCHEMAS.with(|schemas| {
+        schemas.borrow().values()
+            .filter(|schema| matches!(schema.kind, AttributeKind::Enum(_) | AttributeKind::Bool))
+            .map(|schema| schema.key.clone())
+            .collect()
+    });
     
-    if !is_admin {
-        return Err(LandRegistryError::Unauthorized);
+    let mut counts: HashMap<(String, String), u64> = HashMap::new();
+    for (land, _) in lands {
+        let land_type = ("land_type".to_string(), land_type_name(&land.land_type).to_string());
+        *counts.entry(land_type).or_insert(0) += 1;
+        for attribute in land_attributes(land) {
+            if facetable.contains(&attribute.key) {
+                let key = (attribute.key.clone(), attribute_display(&attribute.value));
+                *counts.entry(key).or_insert(0) += 1;
+            }
+        }
     }
     
-    // Clear existing data
-    LAND_STORAGE.with(|storage| storage.borrow_mut().clear());
-    OWNERSHIP_INDEX.with(|index| index.borrow_mut().clear());
-    COORDINATE_INDEX.with(|index| index.borrow_mut().clear());
-    
-    // Restore lands
-    for land in lands {
-        LAND_STORAGE.with(|storage| {
-            storage.borrow_mut().insert(land.id, land.clone());
-        });
+    let mut facets: Vec<FacetCount> = counts.into_iter()
+        .map(|((key, value), count)| FacetCount { key, value, count })
+        .collect();
+    facets.sort_by(|a, b| a.key.cmp(&b.key).then(b.count.cmp(&a.count)).then(a.value.cmp(&b.value)));
+    facets
+}
+
+#[query]
+fn search_lands_faceted(filters: SearchFilters, page: PageRequest) -> FacetedLandPage {
+    let results = run_search(&filters);
+    let facets = facet_counts(&results);
+    FacetedLandPage {
+        page: scored_land_page(results, &page),
+        facets,
+    }
+}
+
+#[query]
+fn search_by_coordinates(min_coords: Coordinates, max_coords: Coordinates, page: PageRequest) -> Page<LandInfo> {
+    let lands: Vec<LandInfo> = LAND_STORAGE.with(|storage| {
+        storage.borrow().values()
+            .filter(|land| {
+                land.coordinates.x >= min_coords.x && land.coordinates.x <= max_coords.x &&
+                land.coordinates.y >= min_coords.y && land.coordinates.y <= max_coords.y &&
+                land.coordinates.z >= min_coords.z && land.coordinates.z <= max_coords.z
+            })
+            .cloned()
+            .collect()
+    });
+    
+    land_page(lands, &page)
+}
+
+#[query]
+fn find_free_space(
+    dimensions: Dimensions,
+    land_type: LandType,
+    search_region: (Coordinates, Coordinates),
+    limit: u32,
+) -> LandRegistryResult<Vec<Coordinates>> {
+    let (min_coords, max_coords) = search_region;
+    
+    if !validate_dimensions(&dimensions) {
+        return Err(LandRegistryError::InvalidDimensions);
+    }
+    
+    if !validate_coordinates(&min_coords) || !validate_coordinates(&max_coords) ||
+       min_coords.x > max_coords.x || min_coords.y > max_coords.y || min_coords.z > max_coords.z {
+        return Err(LandRegistryError::InvalidCoordinates);
+    }
+    
+    let limit = limit.min(MAX_FREE_SPACE_RESULTS) as usize;
+    if limit == 0 {
+        return Ok(Vec::new());
+    }
+    
+    // Lands intersecting the region bound where a free parcel can start
+    let candidates = spatial_candidates(
+        spatial_cell(min_coords.x, min_coords.y),
+        spatial_cell(max_coords.x, max_coords.y),
+    );
+    let nearby: Vec<LandInfo> = LAND_STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        candidates.iter()
+            .filter_map(|id| storage.get(id).cloned())
+            .collect()
+    });
+    
+    // Candidate origins sit at the region corner, flush against an existing parcel, or
+    // just past the edge of a district whose zoning may rule the parcel out
+    let mut xs: Vec<i32> = vec![min_coords.x];
+    let mut ys: Vec<i32> = vec![min_coords.y];
+    let mut zs: Vec<i32> = vec![min_coords.z];
+    for land in &nearby {
+        xs.push(land.coordinates.x + land.dimensions.width as i32);
+        ys.push(land.coordinates.y + land.dimensions.height as i32);
+        zs.push(land.coordinates.z + land.dimensions.depth as i32);
+    }
+    DISTRICTS.with(|districts| {
+        for district in districts.borrow().values() {
+            xs.push(district.max_coords.x);
+            ys.push(district.max_coords.y);
+            zs.push(district.max_coords.z);
+        }
+    });
+    for axis in [&mut xs, &mut ys, &mut zs] {
+        axis.sort_unstable();
+        axis.dedup();
+    }
+    
+    let fits = |value: i32, size: u32, min: i32, max: i32| {
+        value >= min && value + size as i32 <= max
+    };
+    
+    let mut results = Vec::new();
+    let mut checked = 0;
+    'search: for &z in zs.iter().filter(|&&z| fits(z, dimensions.depth, min_coords.z, max_coords.z)) {
+        for &y in ys.iter().filter(|&&y| fits(y, dimensions.height, min_coords.y, max_coords.y)) {
+            for &x in xs.iter().filter(|&&x| fits(x, dimensions.width, min_coords.x, max_coords.x)) {
+                checked += 1;
+                if checked > MAX_FREE_SPACE_CANDIDATES {
+                    break 'search;
+                }
+                
+                // The same placement rules register_land applies
+                let origin = Coordinates { x, y, z };
+                let occupied = nearby.iter().any(|land| {
+                    coordinates_overlap(&origin, &dimensions, &land.coordinates, &land.dimensions)
+                });
+                if !occupied && check_zoning(&origin, &dimensions, &land_type).is_ok() {
+                    results.push(origin);
+                    if results.len() >= limit {
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(results)
+}
+
+// FNV-1a, enough to give each owner a stable colour on the map
+fn owner_hash(owner: &Principal) -> u32 {
+    owner.as_slice().iter().fold(0x811c9dc5u32, |hash, byte| {
+        (hash ^ *byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+#[query]
+fn get_map_chunk(chunk_x: i32, chunk_y: i32, zoom: u8) -> LandRegistryResult<MapChunk> {
+    if zoom > MAX_MAP_ZOOM {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let tile_size = 1i64 << zoom;
+    let chunk_span = tile_size * MAP_CHUNK_TILES;
+    let origin_x = chunk_x as i64 * chunk_span;
+    let origin_y = chunk_y as i64 * chunk_span;
+    
+    let clamp = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    let (min_x, min_y) = (clamp(origin_x), clamp(origin_y));
+    let (max_x, max_y) = (clamp(origin_x + chunk_span - 1), clamp(origin_y + chunk_span - 1));
+    
+    let candidates = spatial_candidates(spatial_cell(min_x, min_y), spatial_cell(max_x, max_y));
+    
+    let mut tiles: HashMap<(u32, u32), MapTile> = HashMap::new();
+    LAND_STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        MARKETPLACE.with(|marketplace| {
+            let marketplace = marketplace.borrow();
+            for land in candidates.iter().filter_map(|id| storage.get(id)) {
+                let land_min_x = land.coordinates.x as i64 - origin_x;
+                let land_min_y = land.coordinates.y as i64 - origin_y;
+                let land_max_x = land_min_x + land.dimensions.width as i64 - 1;
+                let land_max_y = land_min_y + land.dimensions.height as i64 - 1;
+                
+                let first_tx = land_min_x.div_euclid(tile_size).max(0);
+                let first_ty = land_min_y.div_euclid(tile_size).max(0);
+                let last_tx = land_max_x.div_euclid(tile_size).min(MAP_CHUNK_TILES - 1);
+                let last_ty = land_max_y.div_euclid(tile_size).min(MAP_CHUNK_TILES - 1);
+                
+                for tx in first_tx..=last_tx {
+                    for ty in first_ty..=last_ty {
+                        let key = (tx as u32, ty as u32);
+                        let tile = tiles.entry(key).or_insert_with(|| MapTile {
+                            tile_x: key.0,
+                            tile_y: key.1,
+                            land_id: land.id,
+                            land_type: land.land_type.clone(),
+                            for_sale: marketplace.contains_key(&land.id),
+                            owner_hash: owner_hash(&land.owner),
+                            land_count: 0,
+                        });
+                        tile.land_count += 1;
+                        
+                        // Report the lowest id so the same tile renders the same parcel every call
+                        if land.id < tile.land_id {
+                            tile.land_id = land.id;
+                            tile.land_type = land.land_type.clone();
+                            tile.for_sale = marketplace.contains_key(&land.id);
+                            tile.owner_hash = owner_hash(&land.owner);
+                        }
+                    }
+                }
+            }
+        });
+    });
+    
+    let mut tiles: Vec<MapTile> = tiles.into_values().collect();
+    tiles.sort_by_key(|tile| (tile.tile_y, tile.tile_x));
+    
+    Ok(MapChunk {
+        chunk_x,
+        chunk_y,
+        zoom,
+        tile_size: tile_size as u32,
+        origin_x,
+        origin_y,
+        tiles,
+    })
+}
+
+#[query]
+fn get_transaction_history(land_id: Option<LandId>, page: PageRequest) -> Page<TransactionRecord> {
+    let transactions: Vec<(u64, TransactionRecord)> = TRANSACTION_HISTORY.with(|history| {
+        history.borrow().iter()
+            .enumerate()
+            .filter(|(_, tx)| land_id.map(|id| tx.land_id == id).unwrap_or(true))
+            .map(|(index, tx)| (index as u64, tx.clone()))
+            .collect()
+    });
+    
+    transaction_page(transactions, &page)
+}
+
+#[query]
+fn get_price_history(land_id: LandId, page: PageRequest) -> Page<(Timestamp, Price)> {
+    TRANSACTION_HISTORY.with(|history| {
+        position_page(
+            &history.borrow(),
+            &page,
+            |tx| tx.land_id == land_id && tx.price.is_some(),
+            |tx| (tx.timestamp, tx.price.unwrap_or(0)),
+        )
+    })
+}
+
+#[query]
+fn get_land_statistics() -> LandStatistics {
+    let total_lands = LAND_STORAGE.with(|storage| storage.borrow().len() as u64);
+    let total_owners = OWNERSHIP_INDEX.with(|index| index.borrow().len() as u64);
+    let lands_for_sale = MARKETPLACE.with(|marketplace| marketplace.borrow().len() as u64);
+    let total_transactions = TRANSACTION_HISTORY.with(|history| history.borrow().len() as u64);
+    
+    let average_price = if lands_for_sale > 0 {
+        MARKETPLACE.with(|marketplace| {
+            let total: u64 = marketplace.borrow().values().map(|listing| listing.price).sum();
+            Some(total / lands_for_sale)
+        })
+    } else {
+        None
+    };
+    
+    LandStatistics {
+        total_lands,
+        total_owners,
+        lands_for_sale,
+        average_price,
+        total_transactions,
+    }
+}
+
+#[query]
+fn get_lands_near_coordinates(coords: Coordinates, radius: u32, page: PageRequest) -> Page<LandInfo> {
+    let lands: Vec<LandInfo> = LAND_STORAGE.with(|storage| {
+        storage.borrow().values()
+            .filter(|land| {
+                let dx = (land.coordinates.x - coords.x).abs() as u32;
+                let dy = (land.coordinates.y - coords.y).abs() as u32;
+                let dz = (land.coordinates.z - coords.z).abs() as u32;
+                dx <= radius && dy <= radius && dz <= radius
+            })
+            .cloned()
+            .collect()
+    });
+    
+    land_page(lands, &page)
+}
+
+#[update]
+fn update_land_metadata(land_id: LandId, metadata: LandMetadata) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Registration)?;
+    
+    let metadata = Some(metadata);
+    validate_attributes(&metadata)?;
+    
+    let (previous, updated) = LAND_STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        if let Some(land) = storage.get_mut(&land_id) {
+            if !acts_for_owner(land, &caller, OperatorPermission::EditMetadata) {
+                return Err(LandRegistryError::Unauthorized);
+            }
+            ensure_not_held(land)?;
+            let previous = land.clone();
+            land.metadata = metadata;
+            land.last_updated = time();
+            Ok((previous, land.clone()))
+        } else {
+            Err(LandRegistryError::LandNotFound)
+        }
+    })?;
+    
+    unindex_land_text(&previous);
+    index_land_text(&updated);
+    
+    record_audit(caller, AuditAction::MetadataUpdated, AuditTarget::Land(land_id), audit_json(&previous), audit_json(&updated));
+    
+    Ok(())
+}
+
+#[query]
+fn get_marketplace_listing(land_id: LandId) -> Option<MarketplaceListing> {
+    let listing = MARKETPLACE.with(|marketplace| {
+        marketplace.borrow().get(&land_id).cloned()
+    })?;
+    join_listing(&listing)
+}
+
+#[query]
+fn search_marketplace(filters: SearchFilters, page: PageRequest) -> Page<MarketplaceListing> {
+    let filters = SearchFilters { for_sale_only: Some(true), ..filters };
+    let listings: Vec<(MarketplaceListing, u64)> = MARKETPLACE.with(|marketplace| {
+        let marketplace = marketplace.borrow();
+        run_search(&filters).into_iter()
+            .filter_map(|(land, score)| {
+                let listing = marketplace.get(&land.id)?;
+                Some((MarketplaceListing {
+                    land_id: listing.land_id,
+                    seller: listing.seller,
+                    price: listing.price,
+                    listed_at: listing.listed_at,
+                    version: listing_version(&land, listing),
+                    land_info: land,
+                }, score))
+            })
+            .collect()
+    });
+    
+    scored_listing_page(listings, &page)
+}
+
+#[query]
+fn get_user_transactions(user: Principal, page: PageRequest) -> Page<TransactionRecord> {
+    let transactions: Vec<(u64, TransactionRecord)> = TRANSACTION_HISTORY.with(|history| {
+        history.borrow().iter()
+            .enumerate()
+            .filter(|(_, tx)| tx.from == user || tx.to == user)
+            .map(|(index, tx)| (index as u64, tx.clone()))
+            .collect()
+    });
+    
+    transaction_page(transactions, &page)
+}
+
+// Role functions
+fn has_role(principal: &Principal, roles: &[Role]) -> bool {
+    ROLES.with(|assignments| {
+        assignments.borrow().get(principal)
+            .map(|held| held.contains(&Role::SuperAdmin) || roles.iter().any(|role| held.contains(role)))
+            .unwrap_or(false)
+    })
+}
+
+fn super_admin_count() -> usize {
+    ROLES.with(|assignments| {
+        assignments.borrow().values()
+            .filter(|held| held.contains(&Role::SuperAdmin))
+            .count()
+    })
+}
+
+fn record_role_change(principal: Principal, role: Role, granted: bool, actor: Principal) {
+    let (action, before, after) = if granted {
+        (AuditAction::RoleGranted, None, audit_json(&role))
+    } else {
+        (AuditAction::RoleRevoked, audit_json(&role), None)
+    };
+    record_audit(actor, action, AuditTarget::Principal(principal), before, after);
+    
+    ROLE_CHANGES.with(|changes| {
+        changes.borrow_mut().push(RoleChange {
+            principal,
+            role,
+            granted,
+            actor,
+            timestamp: time(),
+        });
+    });
+}
+
+// Removing a SuperAdmin may not leave fewer SuperAdmins than the approval threshold,
+// or M-of-N actions could never be approved again
+fn ensure_super_admins_remain(role: Role) -> LandRegistryResult<()> {
+    if role != Role::SuperAdmin {
+        return Ok(());
+    }
+    let remaining = super_admin_count().saturating_sub(1);
+    if remaining == 0 {
+        return Err(LandRegistryError::LastSuperAdmin);
+    }
+    if (remaining as u32) < required_approvals() {
+        return Err(LandRegistryError::InsufficientApprovers);
+    }
+    Ok(())
+}
+
+// Returns false if the principal already held the role
+fn assign_role(principal: Principal, role: Role, actor: Principal) -> bool {
+    let added = ROLES.with(|assignments| {
+        assignments.borrow_mut().entry(principal).or_default().insert(role)
+    });
+    if added {
+        record_role_change(principal, role, true, actor);
+    }
+    added
+}
+
+fn unassign_role(principal: Principal, role: Role, actor: Principal) -> LandRegistryResult<()> {
+    let holds = ROLES.with(|assignments| {
+        assignments.borrow().get(&principal).map(|held| held.contains(&role)).unwrap_or(false)
+    });
+    if !holds {
+        return Ok(());
+    }
+    ensure_super_admins_remain(role)?;
+    
+    ROLES.with(|assignments| {
+        let mut assignments = assignments.borrow_mut();
+        if let Some(held) = assignments.get_mut(&principal) {
+            held.remove(&role);
+            if held.is_empty() {
+                assignments.remove(&principal);
+            }
+        }
+    });
+    record_role_change(principal, role, false, actor);
+    
+    Ok(())
+}
+
+#[update]
+fn grant_role(principal: Principal, role: Role) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if required_approvals() > 1 {
+        return Err(LandRegistryError::ApprovalRequired);
+    }
+    
+    assign_role(principal, role, caller);
+    
+    Ok(())
+}
+
+#[update]
+fn revoke_role(principal: Principal, role: Role) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if required_approvals() > 1 {
+        return Err(LandRegistryError::ApprovalRequired);
+    }
+    
+    unassign_role(principal, role, caller)
+}
+
+#[query]
+fn get_roles(principal: Principal) -> Vec<Role> {
+    ROLES.with(|assignments| {
+        assignments.borrow().get(&principal)
+            .map(|held| held.iter().copied().collect())
+            .unwrap_or_default()
+    })
+}
+
+#[query]
+fn get_my_roles() -> Vec<Role> {
+    get_roles(system::caller())
+}
+
+#[query]
+fn get_role_assignments() -> LandRegistryResult<Vec<RoleAssignment>> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::SupportAgent]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let mut assignments: Vec<RoleAssignment> = ROLES.with(|assignments| {
+        assignments.borrow().iter()
+            .map(|(principal, held)| RoleAssignment {
+                principal: *principal,
+                roles: held.iter().copied().collect(),
+            })
+            .collect()
+    });
+    assignments.sort_by_key(|assignment| assignment.principal);
+    
+    Ok(assignments)
+}
+
+#[query]
+fn get_role_changes(page: PageRequest) -> LandRegistryResult<Page<RoleChange>> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::SupportAgent]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    Ok(ROLE_CHANGES.with(|changes| position_page(&changes.borrow(), &page, |_| true, RoleChange::clone)))
+}
+
+// Multi-signature approval functions
+fn action_role(action: &AdminAction) -> Role {
+    match action {
+        AdminAction::RemoveLand { .. } |
+        AdminAction::ForceTransfer { .. } => Role::Moderator,
+        AdminAction::RestoreLands(_) |
+        AdminAction::CommitSnapshotImport { .. } |
+        AdminAction::SetApprovalThreshold(_) |
+        AdminAction::GrantRole { .. } |
+        AdminAction::RevokeRole { .. } => Role::SuperAdmin,
+    }
+}
+
+// Actions that remove data or access; these are refused outright while too few
+// principals hold the role to reach the threshold
+fn is_destructive(action: &AdminAction) -> bool {
+    match action {
+        AdminAction::RemoveLand { .. } |
+        AdminAction::RestoreLands(_) |
+        AdminAction::CommitSnapshotImport { .. } |
+        AdminAction::RevokeRole { .. } |
+        AdminAction::ForceTransfer { .. } => true,
+        AdminAction::SetApprovalThreshold(_) |
+        AdminAction::GrantRole { .. } => false,
+    }
+}
+
+// Everyone who may approve an action needing `role`
+fn eligible_approvers(role: Role) -> u32 {
+    ROLES.with(|assignments| {
+        assignments.borrow().values()
+            .filter(|held| held.contains(&Role::SuperAdmin) || held.contains(&role))
+            .count() as u32
+    })
+}
+
+// M of N; the threshold is never lowered to fit the number of eligible approvers
+fn required_approvals() -> u32 {
+    APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow()).max(1)
+}
+
+// Rejects actions that could never execute, so they are not put up for approval
+fn precheck_admin_action(action: &AdminAction) -> LandRegistryResult<()> {
+    match action {
+        AdminAction::RemoveLand { land_id, reason } => {
+            let exists = LAND_STORAGE.with(|storage| storage.borrow().contains_key(land_id));
+            if !exists {
+                return Err(LandRegistryError::LandNotFound);
+            }
+            if reason.trim().is_empty() {
+                return Err(LandRegistryError::InvalidInput);
+            }
+        }
+        AdminAction::RestoreLands(lands) => {
+            if !validate_against_registry(lands).errors.is_empty() {
+                return Err(LandRegistryError::InvalidSnapshot);
+            }
+        }
+        AdminAction::CommitSnapshotImport { checksum } => {
+            let pending = SNAPSHOT_IMPORT.with(|import| {
+                import.borrow().as_ref().map(|import| import.manifest.checksum == *checksum).unwrap_or(false)
+            });
+            if !pending {
+                return Err(LandRegistryError::InvalidSnapshot);
+            }
+        }
+        AdminAction::SetApprovalThreshold(threshold) => {
+            if *threshold == 0 {
+                return Err(LandRegistryError::InvalidInput);
+            }
+            if *threshold as usize > super_admin_count() {
+                return Err(LandRegistryError::InsufficientApprovers);
+            }
+        }
+        AdminAction::GrantRole { principal, role } => {
+            if get_roles(*principal).contains(role) {
+                return Err(LandRegistryError::InvalidInput);
+            }
+        }
+        AdminAction::RevokeRole { principal, role } => {
+            if !get_roles(*principal).contains(role) {
+                return Err(LandRegistryError::InvalidInput);
+            }
+            ensure_super_admins_remain(*role)?;
+        }
+        AdminAction::ForceTransfer { dispute_id, new_owner, .. } => {
+            check_dispute_resolution(*dispute_id, &DisputeOutcome::ForcedTransfer(*new_owner))?;
+        }
+    }
+    Ok(())
+}
+
+fn execute_admin_action(action: AdminAction, proposer: Principal) -> LandRegistryResult<()> {
+    match action {
+        AdminAction::RemoveLand { land_id, reason } => tombstone_land(land_id, reason, proposer),
+        AdminAction::RestoreLands(lands) => replace_lands(lands, proposer),
+        AdminAction::CommitSnapshotImport { checksum } => commit_pending_import(proposer, Some(&checksum)),
+        AdminAction::SetApprovalThreshold(threshold) => {
+            APPROVAL_THRESHOLD.with(|current| *current.borrow_mut() = threshold);
+            Ok(())
+        }
+        AdminAction::GrantRole { principal, role } => {
+            assign_role(principal, role, proposer);
+            Ok(())
+        }
+        AdminAction::RevokeRole { principal, role } => unassign_role(principal, role, proposer),
+        AdminAction::ForceTransfer { dispute_id, new_owner, note } => {
+            settle_dispute(dispute_id, DisputeOutcome::ForcedTransfer(new_owner), note, proposer)
+        }
+    }
+}
+
+// Runs the proposal's action once it has enough approvals
+fn try_execute_admin_proposal(proposal_id: AdminProposalId) -> AdminProposalStatus {
+    let ready = ADMIN_PROPOSALS.with(|proposals| {
+        proposals.borrow().get(&proposal_id)
+            .filter(|proposal| proposal.status == AdminProposalStatus::Pending)
+            .filter(|proposal| proposal.approvals.len() as u32 >= proposal.required_approvals)
+            .map(|proposal| (proposal.action.clone(), proposal.proposer))
+    });
+    
+    if let Some((action, proposer)) = ready {
+        let result = execute_admin_action(action, proposer);
+        // A pause that began after the proposal was approved defers it; unpausing runs it
+        if matches!(result, Err(LandRegistryError::Paused)) {
+            return AdminProposalStatus::Pending;
+        }
+        let resolved = ADMIN_PROPOSALS.with(|proposals| {
+            let mut proposals = proposals.borrow_mut();
+            let proposal = proposals.get_mut(&proposal_id)?;
+            proposal.resolved_at = Some(time());
+            match result {
+                Ok(()) => proposal.status = AdminProposalStatus::Executed,
+                Err(error) => {
+                    proposal.status = AdminProposalStatus::Failed;
+                    proposal.failure = Some(error);
+                }
+            }
+            Some(proposal.status.clone())
+        });
+        record_audit(proposer, AuditAction::AdminActionResolved, AuditTarget::AdminProposal(proposal_id), None, audit_json(&resolved));
+    }
+    
+    ADMIN_PROPOSALS.with(|proposals| {
+        proposals.borrow().get(&proposal_id)
+            .map(|proposal| proposal.status.clone())
+            .unwrap_or(AdminProposalStatus::Cancelled)
+    })
+}
+
+// Approved proposals a pause held back, provided they have not expired in the meantime
+fn run_deferred_admin_proposals() {
+    let now = time();
+    let ready: Vec<AdminProposalId> = ADMIN_PROPOSALS.with(|proposals| {
+        proposals.borrow().values()
+            .filter(|proposal| proposal.status == AdminProposalStatus::Pending && now <= proposal.expires_at)
+            .filter(|proposal| proposal.approvals.len() as u32 >= proposal.required_approvals)
+            .map(|proposal| proposal.id)
+            .collect()
+    });
+    for proposal_id in ready {
+        try_execute_admin_proposal(proposal_id);
+    }
+}
+
+#[update]
+fn propose_admin_action(action: AdminAction) -> LandRegistryResult<AdminProposalId> {
+    let caller = active_caller();
+    let role = action_role(&action);
+    let authorized = has_role(&caller, &[role]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    // The proposer's own approval counts towards the threshold
+    open_admin_proposal(action, caller, vec![caller])
+}
+
+// Shared by role holders proposing directly and by passed governance votes, which propose
+// as the canister and so start without approvals
+fn open_admin_proposal(
+    action: AdminAction,
+    proposer: Principal,
+    approvals: Vec<Principal>,
+) -> LandRegistryResult<AdminProposalId> {
+    precheck_admin_action(&action)?;
+    
+    let required = required_approvals();
+    if is_destructive(&action) && eligible_approvers(action_role(&action)) < required {
+        return Err(LandRegistryError::InsufficientApprovers);
+    }
+    
+    let proposal_id = NEXT_ADMIN_PROPOSAL_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    let now = time();
+    let proposal = AdminProposal {
+        id: proposal_id,
+        action,
+        proposer,
+        approvals,
+        required_approvals: required,
+        status: AdminProposalStatus::Pending,
+        created_at: now,
+        expires_at: now + ADMIN_PROPOSAL_WINDOW_NS,
+        resolved_at: None,
+        failure: None,
+    };
+    
+    ADMIN_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().insert(proposal_id, proposal);
+    });
+    record_audit(proposer, AuditAction::AdminActionProposed, AuditTarget::AdminProposal(proposal_id), None, None);
+    
+    try_execute_admin_proposal(proposal_id);
+    
+    Ok(proposal_id)
+}
+
+#[update]
+fn approve_admin_action(proposal_id: AdminProposalId) -> LandRegistryResult<AdminProposalStatus> {
+    let caller = active_caller();
+    let now = time();
+    
+    // Ok(false) means the approval arrived too late and the proposal has just expired
+    let approved = ADMIN_PROPOSALS.with(|proposals| {
+        let mut proposals = proposals.borrow_mut();
+        let proposal = proposals.get_mut(&proposal_id).ok_or(LandRegistryError::RequestNotFound)?;
+        
+        if !has_role(&caller, &[action_role(&proposal.action)]) {
+            return Err(LandRegistryError::Unauthorized);
+        }
+        if proposal.status != AdminProposalStatus::Pending {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        if now > proposal.expires_at {
+            proposal.status = AdminProposalStatus::Expired;
+            proposal.resolved_at = Some(now);
+            return Ok(false);
+        }
+        if proposal.approvals.contains(&caller) {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        
+        proposal.approvals.push(caller);
+        Ok(true)
+    })?;
+    
+    if !approved {
+        record_audit(
+            caller, AuditAction::AdminActionResolved, AuditTarget::AdminProposal(proposal_id),
+            None, audit_json(&AdminProposalStatus::Expired),
+        );
+        return Err(LandRegistryError::ProposalExpired);
+    }
+    record_audit(caller, AuditAction::AdminActionApproved, AuditTarget::AdminProposal(proposal_id), None, None);
+    
+    Ok(try_execute_admin_proposal(proposal_id))
+}
+
+#[update]
+fn cancel_admin_action(proposal_id: AdminProposalId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    ADMIN_PROPOSALS.with(|proposals| {
+        let mut proposals = proposals.borrow_mut();
+        let proposal = proposals.get_mut(&proposal_id).ok_or(LandRegistryError::RequestNotFound)?;
+        
+        if proposal.proposer != caller && !has_role(&caller, &[Role::SuperAdmin]) {
+            return Err(LandRegistryError::Unauthorized);
+        }
+        if proposal.status != AdminProposalStatus::Pending {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        
+        proposal.status = AdminProposalStatus::Cancelled;
+        proposal.resolved_at = Some(time());
+        Ok(())
+    })?;
+    record_audit(caller, AuditAction::AdminActionCancelled, AuditTarget::AdminProposal(proposal_id), None, None);
+    
+    Ok(())
+}
+
+#[query]
+fn get_admin_proposals(pending_only: bool, page: PageRequest) -> LandRegistryResult<Page<AdminProposal>> {
+    let caller = system::caller();
+    
+    // Any role holder may be asked to approve, so any role holder may look
+    if get_roles(caller).is_empty() {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let now = time();
+    Ok(ADMIN_PROPOSALS.with(|proposals| {
+        id_page(
+            &proposals.borrow(),
+            &page,
+            |proposal| !pending_only || (proposal.status == AdminProposalStatus::Pending && now <= proposal.expires_at),
+            AdminProposal::clone,
+        )
+    }))
+}
+
+#[query]
+fn get_approval_threshold() -> u32 {
+    APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow())
+}
+
+// Governance functions
+fn land_voting_power(land: &LandInfo, basis: &VotingPowerBasis) -> u64 {
+    match basis {
+        VotingPowerBasis::LandArea => calculate_land_area(&land.dimensions) as u64,
+        VotingPowerBasis::ParcelCount => 1,
+    }
+}
+
+// Only parcels registered before `cutoff` carry power, so land registered after a
+// proposal was submitted cannot be used to swing it
+fn total_voting_power(cutoff: Timestamp) -> u64 {
+    let basis = GOVERNANCE_CONFIG.with(|config| config.borrow().voting_power_basis.clone());
+    LAND_STORAGE.with(|storage| {
+        storage.borrow().values()
+            .filter(|land| land.created_at < cutoff)
+            .map(|land| land_voting_power(land, &basis))
+            .sum()
+    })
+}
+
+// Parcels currently owned by `voter` and registered before `cutoff`, paired with the power each carries
+fn voter_lands(voter: &Principal, cutoff: Timestamp) -> Vec<(LandId, u64)> {
+    let basis = GOVERNANCE_CONFIG.with(|config| config.borrow().voting_power_basis.clone());
+    let land_ids = OWNERSHIP_INDEX.with(|index| index.borrow().get(voter).cloned().unwrap_or_default());
+    LAND_STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        land_ids.iter()
+            .filter_map(|id| storage.get(id))
+            .filter(|land| land.created_at < cutoff)
+            .map(|land| (land.id, land_voting_power(land, &basis)))
+            .collect()
+    })
+}
+
+fn validate_governance_action(action: &GovernanceAction) -> LandRegistryResult<()> {
+    match action {
+        GovernanceAction::SetWorldBounds { world_min, world_max } => {
+            if world_min.x >= world_max.x || world_min.y >= world_max.y || world_min.z >= world_max.z {
+                return Err(LandRegistryError::InvalidCoordinates);
+            }
+            // Shrinking the world may not strand any part of an existing parcel
+            let strands_land = LAND_STORAGE.with(|storage| {
+                storage.borrow().values()
+                    .any(|land| !footprint_within_bounds(&land.coordinates, &land.dimensions, world_min, world_max))
+            });
+            if strands_land {
+                return Err(LandRegistryError::InvalidCoordinates);
+            }
+        }
+        GovernanceAction::SetDistrictZoning { district_id, zoning } => {
+            let exists = DISTRICTS.with(|districts| districts.borrow().contains_key(district_id));
+            if !exists {
+                return Err(LandRegistryError::DistrictNotFound);
+            }
+            if zoning.allowed_land_types.is_empty() {
+                return Err(LandRegistryError::InvalidInput);
+            }
+        }
+        GovernanceAction::GrantRole { .. } | GovernanceAction::RevokeRole { .. } => {
+            if let Some(admin_action) = role_change_action(action) {
+                precheck_admin_action(&admin_action)?;
+            }
+        }
+        GovernanceAction::SetGovernanceConfig(config) => {
+            if config.quorum_bps > 10_000 || config.voting_period_ns < MIN_VOTING_PERIOD_NS {
+                return Err(LandRegistryError::InvalidInput);
+            }
+        }
+        GovernanceAction::ApproveRezoning { request_id } => {
+            let pending = REZONING_REQUESTS.with(|requests| {
+                requests.borrow().get(request_id).map(|request| request.status == RezoningStatus::Pending)
+            });
+            match pending {
+                None => return Err(LandRegistryError::RequestNotFound),
+                Some(false) => return Err(LandRegistryError::InvalidInput),
+                Some(true) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+fn role_change_action(action: &GovernanceAction) -> Option<AdminAction> {
+    match action {
+        GovernanceAction::GrantRole { principal, role } => Some(AdminAction::GrantRole { principal: *principal, role: *role }),
+        GovernanceAction::RevokeRole { principal, role } => Some(AdminAction::RevokeRole { principal: *principal, role: *role }),
+        _ => None,
+    }
+}
+
+fn execute_governance_action(action: GovernanceAction) -> LandRegistryResult<()> {
+    // Conditions may have changed during the voting period
+    validate_governance_action(&action)?;
+    
+    let actor = system::id();
+    match action {
+        GovernanceAction::SetWorldBounds { world_min, world_max } => {
+            REGISTRY_PARAMETERS.with(|parameters| {
+                let mut parameters = parameters.borrow_mut();
+                parameters.world_min = world_min;
+                parameters.world_max = world_max;
+            });
+        }
+        GovernanceAction::SetDistrictZoning { district_id, zoning } => {
+            DISTRICTS.with(|districts| {
+                if let Some(district) = districts.borrow_mut().get_mut(&district_id) {
+                    district.zoning = zoning;
+                    district.last_updated = time();
+                }
+            });
+        }
+        // A vote cannot change roles on its own; it opens an admin proposal that role holders
+        // still approve like any other
+        GovernanceAction::GrantRole { .. } | GovernanceAction::RevokeRole { .. } => {
+            if let Some(admin_action) = role_change_action(&action) {
+                open_admin_proposal(admin_action, actor, Vec::new())?;
+            }
+        }
+        GovernanceAction::SetGovernanceConfig(config) => {
+            GOVERNANCE_CONFIG.with(|current| *current.borrow_mut() = config);
+        }
+        GovernanceAction::ApproveRezoning { request_id } => {
+            apply_rezoning(request_id, actor, Some("Approved by governance vote".to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+// Tallies and resolves every open proposal whose voting period has ended
+fn process_governance_deadlines() {
+    let now = time();
+    let mut due: Vec<GovernanceProposal> = GOVERNANCE_PROPOSALS.with(|proposals| {
+        proposals.borrow().values()
+            .filter(|proposal| proposal.status == GovernanceProposalStatus::Open && proposal.voting_ends_at <= now)
+            .cloned()
+            .collect()
+    });
+    due.sort_by_key(|proposal| proposal.id);
+    
+    for proposal in due {
+        let turnout = proposal.yes_power + proposal.no_power;
+        let passed = turnout >= proposal.quorum && proposal.yes_power > proposal.no_power;
+        let (status, failure) = if !passed {
+            (GovernanceProposalStatus::Rejected, None)
+        } else {
+            match execute_governance_action(proposal.action) {
+                Ok(()) => (GovernanceProposalStatus::Executed, None),
+                Err(error) => (GovernanceProposalStatus::Failed, Some(error)),
+            }
+        };
+        
+        record_audit(
+            system::id(), AuditAction::GovernanceProposalResolved, AuditTarget::GovernanceProposal(proposal.id),
+            None, audit_json(&status),
+        );
+        GOVERNANCE_PROPOSALS.with(|proposals| {
+            if let Some(stored) = proposals.borrow_mut().get_mut(&proposal.id) {
+                stored.status = status;
+                stored.failure = failure;
+                stored.resolved_at = Some(now);
+            }
+        });
+    }
+}
+
+#[update]
+fn submit_governance_proposal(
+    title: String,
+    description: String,
+    action: GovernanceAction,
+) -> LandRegistryResult<GovernanceProposalId> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::All)?;
+    
+    if title.trim().is_empty() || title.len() > MAX_PROPOSAL_TITLE_LENGTH ||
+       description.len() > MAX_PROPOSAL_DESCRIPTION_LENGTH {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let now = time();
+    let config = GOVERNANCE_CONFIG.with(|config| config.borrow().clone());
+    let power: u64 = voter_lands(&caller, now).iter().map(|(_, power)| power).sum();
+    if power == 0 || power < config.min_proposer_power {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    validate_governance_action(&action)?;
+    
+    let proposal_id = NEXT_GOVERNANCE_PROPOSAL_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    let quorum = (total_voting_power(now) as u128 * config.quorum_bps as u128 / 10_000) as u64;
+    let proposal = GovernanceProposal {
+        id: proposal_id,
+        proposer: caller,
+        title,
+        description,
+        action,
+        votes: Vec::new(),
+        counted_lands: Vec::new(),
+        yes_power: 0,
+        no_power: 0,
+        quorum,
+        created_at: now,
+        voting_ends_at: now + config.voting_period_ns,
+        status: GovernanceProposalStatus::Open,
+        resolved_at: None,
+        failure: None,
+    };
+    
+    GOVERNANCE_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().insert(proposal_id, proposal);
+    });
+    record_audit(caller, AuditAction::GovernanceProposalSubmitted, AuditTarget::GovernanceProposal(proposal_id), None, None);
+    
+    schedule_next_timer();
+    
+    Ok(proposal_id)
+}
+
+// Casts the caller's not-yet-counted parcels for or against; returns the power used
+#[update]
+fn vote_on_proposal(proposal_id: GovernanceProposalId, approve: bool) -> LandRegistryResult<u64> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::All)?;
+    
+    let now = time();
+    
+    GOVERNANCE_PROPOSALS.with(|proposals| {
+        let mut proposals = proposals.borrow_mut();
+        let proposal = proposals.get_mut(&proposal_id).ok_or(LandRegistryError::RequestNotFound)?;
+        
+        if proposal.status != GovernanceProposalStatus::Open || now >= proposal.voting_ends_at {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        
+        let lands = voter_lands(&caller, proposal.created_at);
+        
+        let counted: HashSet<LandId> = proposal.counted_lands.iter().copied().collect();
+        let fresh: Vec<(LandId, u64)> = lands.into_iter()
+            .filter(|(id, _)| !counted.contains(id))
+            .collect();
+        let power: u64 = fresh.iter().map(|(_, power)| power).sum();
+        if power == 0 {
+            return Err(LandRegistryError::Unauthorized);
+        }
+        
+        proposal.counted_lands.extend(fresh.iter().map(|(id, _)| *id));
+        if approve {
+            proposal.yes_power += power;
+        } else {
+            proposal.no_power += power;
+        }
+        proposal.votes.push(GovernanceVote {
+            voter: caller,
+            approve,
+            power,
+            timestamp: now,
+        });
+        
+        Ok(power)
+    })
+}
+
+#[query]
+fn get_governance_proposal(proposal_id: GovernanceProposalId) -> Option<GovernanceProposal> {
+    GOVERNANCE_PROPOSALS.with(|proposals| proposals.borrow().get(&proposal_id).cloned())
+}
+
+#[query]
+fn get_governance_proposals(open_only: bool, page: PageRequest) -> Page<GovernanceProposal> {
+    GOVERNANCE_PROPOSALS.with(|proposals| {
+        id_page(
+            &proposals.borrow(),
+            &page,
+            |proposal| !open_only || proposal.status == GovernanceProposalStatus::Open,
+            GovernanceProposal::clone,
+        )
+    })
+}
+
+#[query]
+fn get_voting_power(voter: Principal) -> u64 {
+    voter_lands(&voter, Timestamp::MAX).iter().map(|(_, power)| power).sum()
+}
+
+#[query]
+fn get_governance_config() -> GovernanceConfig {
+    GOVERNANCE_CONFIG.with(|config| config.borrow().clone())
+}
+
+#[query]
+fn get_registry_parameters() -> RegistryParameters {
+    REGISTRY_PARAMETERS.with(|parameters| parameters.borrow().clone())
+}
+
+// Hold and dispute functions
+fn hold_is_active(hold: &LandHold, now: Timestamp) -> bool {
+    hold.expires_at.map(|expires_at| expires_at > now).unwrap_or(true)
+}
+
+fn ensure_not_held(land: &LandInfo) -> LandRegistryResult<()> {
+    let now = time();
+    if land.holds.iter().flatten().any(|hold| hold_is_active(hold, now)) {
+        Err(LandRegistryError::LandHeld)
+    } else {
+        Ok(())
+    }
+}
+
+// Drops the parcel's expired holds and returns them
+fn prune_expired_holds(land: &mut LandInfo, now: Timestamp) -> Vec<LandHold> {
+    let Some(holds) = land.holds.take() else {
+        return Vec::new();
+    };
+    let (active, expired): (Vec<LandHold>, Vec<LandHold>) = holds.into_iter()
+        .partition(|hold| hold_is_active(hold, now));
+    land.holds = if active.is_empty() { None } else { Some(active) };
+    expired
+}
+
+fn record_expired_holds(land_id: LandId, expired: &[LandHold]) {
+    for hold in expired {
+        record_audit(system::id(), AuditAction::HoldReleased, AuditTarget::Land(land_id), audit_json(hold), None);
+    }
+}
+
+// Holds are also pruned whenever their parcel's holds are touched; the timer catches the rest
+fn release_expired_holds() {
+    let now = time();
+    let expired: Vec<(LandId, Vec<LandHold>)> = LAND_STORAGE.with(|storage| {
+        storage.borrow_mut().values_mut()
+            .filter(|land| land.holds.iter().flatten().any(|hold| !hold_is_active(hold, now)))
+            .map(|land| (land.id, prune_expired_holds(land, now)))
+            .collect()
+    });
+    for (land_id, holds) in expired {
+        record_expired_holds(land_id, &holds);
+    }
+}
+
+// Liens are a Treasurer matter; freezes and dispute holds belong to Moderators
+fn hold_role(kind: &HoldKind) -> Role {
+    match kind {
+        HoldKind::Lien => Role::Treasurer,
+        HoldKind::Freeze | HoldKind::Dispute => Role::Moderator,
+    }
+}
+
+// Moves a parcel to `new_owner` outside the normal transfer path, keeping every index in step.
+// The caller certifies the change.
+fn reassign_land(land_id: LandId, new_owner: Principal) -> Option<(LandInfo, LandInfo)> {
+    let (previous, updated) = set_land_owner(land_id, new_owner, time())?;
+    clear_operators(land_id);
+    cancel_pending_rezoning(land_id, system::id(), "Parcel changed hands");
+    
+    Some((previous, updated))
+}
+
+#[update]
+fn place_hold(
+    land_id: LandId,
+    kind: HoldKind,
+    reason: String,
+    dispute_id: Option<DisputeId>,
+    expires_at: Option<Timestamp>,
+) -> LandRegistryResult<HoldId> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[hold_role(&kind)]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let now = time();
+    if reason.trim().is_empty() || expires_at.map(|expires_at| expires_at <= now).unwrap_or(false) {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let exists = LAND_STORAGE.with(|storage| storage.borrow().contains_key(&land_id));
+    if !exists {
+        return Err(LandRegistryError::LandNotFound);
+    }
+    
+    // A dispute hold must point at an open dispute over the same parcel
+    if let Some(dispute_id) = dispute_id {
+        let matches = DISPUTES.with(|disputes| {
+            disputes.borrow().get(&dispute_id)
+                .map(|dispute| dispute.land_id == land_id && dispute.status == DisputeStatus::Open)
+                .unwrap_or(false)
+        });
+        if !matches {
+            return Err(LandRegistryError::RequestNotFound);
+        }
+    }
+    
+    let hold_id = NEXT_HOLD_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    let hold = LandHold {
+        id: hold_id,
+        kind,
+        reason,
+        dispute_id,
+        placed_by: caller,
+        placed_at: now,
+        expires_at,
+    };
+    let after = audit_json(&hold);
+    
+    let expired = LAND_STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let land = storage.get_mut(&land_id).expect("land checked above");
+        let expired = prune_expired_holds(land, now);
+        land.holds.get_or_insert_with(Vec::new).push(hold);
+        expired
+    });
+    
+    record_expired_holds(land_id, &expired);
+    record_audit(caller, AuditAction::HoldPlaced, AuditTarget::Land(land_id), None, after);
+    
+    if expires_at.is_some() {
+        schedule_next_timer();
+    }
+    
+    Ok(hold_id)
+}
+
+#[update]
+fn release_hold(land_id: LandId, hold_id: HoldId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let expired = LAND_STORAGE.with(|storage| {
+        storage.borrow_mut().get_mut(&land_id).map(|land| prune_expired_holds(land, time()))
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    record_expired_holds(land_id, &expired);
+    
+    let released = LAND_STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let land = storage.get_mut(&land_id).ok_or(LandRegistryError::LandNotFound)?;
+        let holds = land.holds.as_mut().ok_or(LandRegistryError::RequestNotFound)?;
+        let position = holds.iter()
+            .position(|hold| hold.id == hold_id)
+            .ok_or(LandRegistryError::RequestNotFound)?;
+        
+        if !has_role(&caller, &[hold_role(&holds[position].kind)]) {
+            return Err(LandRegistryError::Unauthorized);
+        }
+        
+        let released = holds.remove(position);
+        if holds.is_empty() {
+            land.holds = None;
+        }
+        Ok(released)
+    })?;
+    
+    record_audit(caller, AuditAction::HoldReleased, AuditTarget::Land(land_id), audit_json(&released), None);
+    
+    Ok(())
+}
+
+#[update]
+fn open_dispute(land_id: LandId, statement: String) -> LandRegistryResult<DisputeId> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::All)?;
+    
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    if caller == Principal::anonymous() || caller == land_info.owner {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if statement.trim().is_empty() || statement.len() > MAX_DISPUTE_TEXT_LENGTH {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let open_disputes = DISPUTES.with(|disputes| {
+        disputes.borrow().values()
+            .filter(|dispute| dispute.claimant == caller && dispute.status == DisputeStatus::Open)
+            .count()
+    });
+    if open_disputes >= MAX_OPEN_DISPUTES_PER_CLAIMANT {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let dispute_id = NEXT_DISPUTE_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    // Opening a dispute does not freeze the parcel; a Moderator decides whether to place a hold
+    let dispute = Dispute {
+        id: dispute_id,
+        land_id,
+        claimant: caller,
+        respondent: land_info.owner,
+        statement,
+        evidence: Vec::new(),
+        status: DisputeStatus::Open,
+        outcome: None,
+        resolution_note: None,
+        resolved_by: None,
+        opened_at: time(),
+        resolved_at: None,
+    };
+    
+    record_audit(caller, AuditAction::DisputeOpened, AuditTarget::Dispute(dispute_id), None, audit_json(&dispute));
+    
+    DISPUTES.with(|disputes| {
+        disputes.borrow_mut().insert(dispute_id, dispute);
+    });
+    
+    Ok(dispute_id)
+}
+
+#[update]
+fn submit_dispute_evidence(dispute_id: DisputeId, content: String) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::All)?;
+    
+    if content.trim().is_empty() || content.len() > MAX_DISPUTE_TEXT_LENGTH {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let moderator = has_role(&caller, &[Role::Moderator]);
+    let evidence = DisputeEvidence {
+        submitted_by: caller,
+        content,
+        submitted_at: time(),
+    };
+    let after = audit_json(&evidence);
+    
+    DISPUTES.with(|disputes| {
+        let mut disputes = disputes.borrow_mut();
+        let dispute = disputes.get_mut(&dispute_id).ok_or(LandRegistryError::RequestNotFound)?;
+        
+        if caller != dispute.claimant && caller != dispute.respondent && !moderator {
+            return Err(LandRegistryError::Unauthorized);
+        }
+        if dispute.status != DisputeStatus::Open || dispute.evidence.len() >= MAX_DISPUTE_EVIDENCE {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        
+        dispute.evidence.push(evidence);
+        Ok(())
+    })?;
+    
+    record_audit(caller, AuditAction::DisputeEvidenceSubmitted, AuditTarget::Dispute(dispute_id), None, after);
+    
+    Ok(())
+}
+
+// Resolving releases the dispute's holds; a ForcedTransfer outcome moves the parcel
+// regardless of any other hold on it and is recorded in the transaction history.
+// Forced transfers need M-of-N approval like any other destructive action.
+#[update]
+fn resolve_dispute(dispute_id: DisputeId, outcome: DisputeOutcome, note: Option<String>) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if matches!(outcome, DisputeOutcome::ForcedTransfer(_)) && required_approvals() > 1 {
+        return Err(LandRegistryError::ApprovalRequired);
+    }
+    
+    settle_dispute(dispute_id, outcome, note, caller)
+}
+
+// Returns the dispute if it can be resolved with `outcome`
+fn check_dispute_resolution(dispute_id: DisputeId, outcome: &DisputeOutcome) -> LandRegistryResult<Dispute> {
+    let dispute = DISPUTES.with(|disputes| {
+        disputes.borrow().get(&dispute_id).cloned()
+    }).ok_or(LandRegistryError::RequestNotFound)?;
+    
+    if dispute.status != DisputeStatus::Open {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    if let DisputeOutcome::ForcedTransfer(new_owner) = outcome {
+        let land_exists = LAND_STORAGE.with(|storage| storage.borrow().contains_key(&dispute.land_id));
+        if !land_exists {
+            return Err(LandRegistryError::LandNotFound);
+        }
+        if *new_owner == Principal::anonymous() {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        // Like a recovery, a forced transfer waits out a transfer pause
+        ensure_not_paused(PauseScope::Transfers)?;
+    }
+    
+    Ok(dispute)
+}
+
+fn settle_dispute(
+    dispute_id: DisputeId,
+    outcome: DisputeOutcome,
+    note: Option<String>,
+    actor: Principal,
+) -> LandRegistryResult<()> {
+    let dispute = check_dispute_resolution(dispute_id, &outcome)?;
+    
+    // Release the holds placed for this dispute
+    let released: Vec<LandHold> = LAND_STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        match storage.get_mut(&dispute.land_id) {
+            Some(land) => {
+                let (released, kept): (Vec<LandHold>, Vec<LandHold>) = land.holds.take().unwrap_or_default()
+                    .into_iter()
+                    .partition(|hold| hold.dispute_id == Some(dispute_id));
+                land.holds = if kept.is_empty() { None } else { Some(kept) };
+                released
+            }
+            None => Vec::new(),
+        }
+    });
+    for hold in &released {
+        record_audit(actor, AuditAction::HoldReleased, AuditTarget::Land(dispute.land_id), audit_json(hold), None);
+    }
+    
+    if let DisputeOutcome::ForcedTransfer(new_owner) = &outcome {
+        let now = time();
+        if let Some((previous, updated)) = reassign_land(dispute.land_id, *new_owner) {
+            let transaction = TransactionRecord {
+                land_id: dispute.land_id,
+                from: previous.owner,
+                to: *new_owner,
+                price: None,
+                transaction_type: TransactionType::ForcedTransfer { dispute_id },
+                timestamp: now,
+            };
+            
+            TRANSACTION_HISTORY.with(|history| {
+                history.borrow_mut().push(transaction);
+            });
+            
+            record_audit(
+                actor, AuditAction::LandForceTransferred, AuditTarget::Land(dispute.land_id),
+                audit_json(&previous), audit_json(&updated),
+            );
+            certify_land(dispute.land_id);
+        }
+    }
+    
+    let resolved = DISPUTES.with(|disputes| {
+        let mut disputes = disputes.borrow_mut();
+        let stored = disputes.get_mut(&dispute_id)?;
+        stored.status = DisputeStatus::Resolved;
+        stored.outcome = Some(outcome);
+        stored.resolution_note = note;
+        stored.resolved_by = Some(actor);
+        stored.resolved_at = Some(time());
+        Some(stored.clone())
+    });
+    
+    record_audit(actor, AuditAction::DisputeResolved, AuditTarget::Dispute(dispute_id), audit_json(&dispute), audit_json(&resolved));
+    
+    Ok(())
+}
+
+#[query]
+fn get_dispute(dispute_id: DisputeId) -> Option<Dispute> {
+    DISPUTES.with(|disputes| disputes.borrow().get(&dispute_id).cloned())
+}
+
+#[query]
+fn get_disputes_for_land(land_id: LandId, page: PageRequest) -> Page<Dispute> {
+    DISPUTES.with(|disputes| {
+        id_page(&disputes.borrow(), &page, |dispute| dispute.land_id == land_id, Dispute::clone)
+    })
+}
+
+#[query]
+fn get_open_disputes(page: PageRequest) -> Page<Dispute> {
+    DISPUTES.with(|disputes| {
+        id_page(&disputes.borrow(), &page, |dispute| dispute.status == DisputeStatus::Open, Dispute::clone)
+    })
+}
+
+// Operator functions
+fn operator_grant_active(grant: &OperatorGrant, land: &LandInfo, now: Timestamp) -> bool {
+    grant.granted_by == land.owner &&
+    grant.expires_at.map(|expires_at| expires_at > now).unwrap_or(true)
+}
+
+// True for the owner, or for an operator the current owner granted `permission`
+fn acts_for_owner(land: &LandInfo, caller: &Principal, permission: OperatorPermission) -> bool {
+    if land.owner == *caller {
+        return true;
+    }
+    let now = time();
+    OPERATORS.with(|operators| {
+        operators.borrow().get(&land.id)
+            .and_then(|grants| grants.get(caller))
+            .map(|grant| operator_grant_active(grant, land, now) && grant.permissions.contains(&permission))
+            .unwrap_or(false)
+    })
+}
+
+// True for anyone holding an active grant on the parcel, whatever its permissions
+fn is_operator(land: &LandInfo, caller: &Principal) -> bool {
+    let now = time();
+    OPERATORS.with(|operators| {
+        operators.borrow().get(&land.id)
+            .and_then(|grants| grants.get(caller))
+            .map(|grant| operator_grant_active(grant, land, now))
+            .unwrap_or(false)
+    })
+}
+
+fn clear_operators(land_id: LandId) {
+    OPERATORS.with(|operators| {
+        operators.borrow_mut().remove(&land_id);
+    });
+}
+
+// Replaces any earlier grant to the same operator
+#[update]
+fn grant_operator(
+    land_id: LandId,
+    operator: Principal,
+    permissions: Vec<OperatorPermission>,
+    expires_at: Option<Timestamp>,
+) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::All)?;
+    
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    if land_info.owner != caller {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let now = time();
+    if operator == caller || operator == Principal::anonymous() || permissions.is_empty() ||
+       expires_at.map(|expires_at| expires_at <= now).unwrap_or(false) {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let mut unique_permissions = Vec::new();
+    for permission in permissions {
+        if !unique_permissions.contains(&permission) {
+            unique_permissions.push(permission);
+        }
+    }
+    let grant = OperatorGrant {
+        land_id,
+        operator,
+        permissions: unique_permissions,
+        granted_by: caller,
+        granted_at: now,
+        expires_at,
+    };
+    
+    let after = audit_json(&grant);
+    let previous = OPERATORS.with(|operators| {
+        operators.borrow_mut().entry(land_id).or_default().insert(operator, grant)
+    });
+    
+    record_audit(caller, AuditAction::OperatorGranted, AuditTarget::Land(land_id), audit_json(&previous), after);
+    
+    Ok(())
+}
+
+// The owner may revoke a grant, and an operator may resign
+#[update]
+fn revoke_operator(land_id: LandId, operator: Principal) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let owner = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).map(|land| land.owner)
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    if caller != owner && caller != operator {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let removed = OPERATORS.with(|operators| {
+        let mut operators = operators.borrow_mut();
+        let grants = operators.get_mut(&land_id)?;
+        let removed = grants.remove(&operator);
+        if grants.is_empty() {
+            operators.remove(&land_id);
+        }
+        removed
+    }).ok_or(LandRegistryError::RequestNotFound)?;
+    
+    record_audit(caller, AuditAction::OperatorRevoked, AuditTarget::Land(land_id), audit_json(&removed), None);
+    
+    Ok(())
+}
+
+#[query]
+fn get_land_operators(land_id: LandId) -> Vec<OperatorGrant> {
+    let Some(land) = get_land(land_id) else {
+        return Vec::new();
+    };
+    let now = time();
+    let mut grants: Vec<OperatorGrant> = OPERATORS.with(|operators| {
+        operators.borrow().get(&land_id)
+            .map(|grants| {
+                grants.values()
+                    .filter(|grant| operator_grant_active(grant, &land, now))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    });
+    grants.sort_by_key(|grant| grant.operator);
+    grants
+}
+
+#[query]
+fn get_operated_lands(operator: Principal, page: PageRequest) -> Page<OperatorGrant> {
+    let now = time();
+    OPERATORS.with(|operators| {
+        LAND_STORAGE.with(|storage| {
+            let storage = storage.borrow();
+            let active_grant = |grants: &HashMap<Principal, OperatorGrant>| {
+                grants.get(&operator).filter(|grant| {
+                    storage.get(&grant.land_id)
+                        .map(|land| operator_grant_active(grant, land, now))
+                        .unwrap_or(false)
+                })
+                .cloned()
+            };
+            id_page(
+                &operators.borrow(),
+                &page,
+                |grants| active_grant(grants).is_some(),
+                |grants| active_grant(grants).expect("filtered to active grants"),
+            )
+        })
+    })
+}
+
+// Offer functions
+fn clear_offers(land_id: LandId) {
+    OFFERS.with(|offers| offers.borrow_mut().retain(|_, offer| offer.land_id != land_id));
+}
+
+#[update]
+fn make_offer(land_id: LandId, price: Price) -> LandRegistryResult<OfferId> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Marketplace)?;
+    
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    // The owner's side may not bid on the parcel it controls
+    if caller == Principal::anonymous() || caller == land_info.owner || is_operator(&land_info, &caller) {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    if price == 0 {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    // One standing offer per buyer and parcel; a new price means withdrawing the old offer
+    let already_offered = OFFERS.with(|offers| {
+        offers.borrow().values().any(|offer| offer.land_id == land_id && offer.buyer == caller)
+    });
+    if already_offered {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let offer_id = NEXT_OFFER_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    let offer = Offer {
+        id: offer_id,
+        land_id,
+        buyer: caller,
+        price,
+        made_at: time(),
+    };
+    let after = audit_json(&offer);
+    
+    OFFERS.with(|offers| {
+        offers.borrow_mut().insert(offer_id, offer);
+    });
+    record_audit(caller, AuditAction::OfferMade, AuditTarget::Offer(offer_id), None, after);
+    
+    Ok(offer_id)
+}
+
+// Like the other withdrawals, allowed while the registry is paused
+#[update]
+fn withdraw_offer(offer_id: OfferId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let offer = OFFERS.with(|offers| {
+        offers.borrow().get(&offer_id).cloned()
+    }).ok_or(LandRegistryError::RequestNotFound)?;
+    
+    if offer.buyer != caller {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    OFFERS.with(|offers| offers.borrow_mut().remove(&offer_id));
+    record_audit(caller, AuditAction::OfferWithdrawn, AuditTarget::Offer(offer_id), audit_json(&offer), None);
+    
+    Ok(())
+}
+
+// The owner, or an operator granted AcceptOffers, sells the parcel to the bidder at the
+// offered price
+#[update]
+fn accept_offer(offer_id: OfferId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Marketplace)?;
+    ensure_not_paused(PauseScope::Transfers)?;
+    
+    let offer = OFFERS.with(|offers| {
+        offers.borrow().get(&offer_id).cloned()
+    }).ok_or(LandRegistryError::RequestNotFound)?;
+    
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&offer.land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    if !acts_for_owner(&land_info, &caller, OperatorPermission::AcceptOffers) {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    // The bidder may have been made an operator since bidding
+    if is_operator(&land_info, &offer.buyer) {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    ensure_not_held(&land_info)?;
+    
+    // Payment is simulated, as in buy_land. The new owner also takes the parcel off the
+    // marketplace and drops every other offer on it
+    let land_id = offer.land_id;
+    let updated = set_land_owner(land_id, offer.buyer, time()).map(|(_, updated)| updated);
+    record_audit(caller, AuditAction::OfferAccepted, AuditTarget::Offer(offer_id), audit_json(&offer), None);
+    record_audit(caller, AuditAction::LandSold, AuditTarget::Land(land_id), audit_json(&land_info), audit_json(&updated));
+    clear_operators(land_id);
+    cancel_pending_rezoning(land_id, caller, "Parcel changed hands");
+    
+    let transaction = TransactionRecord {
+        land_id,
+        from: land_info.owner,
+        to: offer.buyer,
+        price: Some(offer.price),
+        transaction_type: TransactionType::Sale,
+        timestamp: time(),
+    };
+    
+    TRANSACTION_HISTORY.with(|history| {
+        history.borrow_mut().push(transaction);
+    });
+    
+    certify_land(land_id);
+    
+    Ok(())
+}
+
+#[query]
+fn get_offers_for_land(land_id: LandId, page: PageRequest) -> Page<Offer> {
+    OFFERS.with(|offers| {
+        id_page(&offers.borrow(), &page, |offer| offer.land_id == land_id, Offer::clone)
+    })
+}
+
+// Guardian recovery functions
+
+// Every update endpoint takes its caller from here, so any call counts as activity for the
+// owner's dead-man switch
+fn active_caller() -> Principal {
+    let caller = system::caller();
+    touch_activity(caller);
+    caller
+}
+
+fn touch_activity(principal: Principal) {
+    let has_guardians = GUARDIANS.with(|guardians| guardians.borrow().contains_key(&principal));
+    if has_guardians {
+        LAST_ACTIVITY.with(|activity| {
+            activity.borrow_mut().insert(principal, time());
+        });
+    }
+}
+
+fn owner_inactive(config: &GuardianConfig, now: Timestamp) -> bool {
+    let last_active = LAST_ACTIVITY.with(|activity| activity.borrow().get(&config.owner).copied())
+        .unwrap_or(config.updated_at);
+    now.saturating_sub(last_active) >= config.inactivity_period_ns
+}
+
+fn active_recovery_for(owner: &Principal) -> Option<RecoveryRequestId> {
+    RECOVERY_REQUESTS.with(|requests| {
+        requests.borrow().values()
+            .find(|request| {
+                request.owner == *owner &&
+                matches!(request.status, RecoveryStatus::Pending | RecoveryStatus::Challenge)
+            })
+            .map(|request| request.id)
+    })
+}
+
+#[update]
+fn set_guardians(
+    guardians: Vec<Principal>,
+    threshold: u32,
+    inactivity_period_ns: u64,
+    challenge_period_ns: u64,
+) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::All)?;
+    
+    if caller == Principal::anonymous() {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let mut unique_guardians: Vec<Principal> = guardians.into_iter()
+        .filter(|guardian| *guardian != caller && *guardian != Principal::anonymous())
+        .collect();
+    unique_guardians.sort();
+    unique_guardians.dedup();
+    
+    if unique_guardians.is_empty() || unique_guardians.len() > MAX_GUARDIANS ||
+       threshold == 0 || threshold as usize > unique_guardians.len() ||
+       inactivity_period_ns < MIN_INACTIVITY_PERIOD_NS || challenge_period_ns < MIN_CHALLENGE_PERIOD_NS {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    // Changing guardians mid-recovery would let a new set finish an old request
+    if active_recovery_for(&caller).is_some() {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let now = time();
+    let config = GuardianConfig {
+        owner: caller,
+        guardians: unique_guardians,
+        threshold,
+        inactivity_period_ns,
+        challenge_period_ns,
+        updated_at: now,
+    };
+    
+    let after = audit_json(&config);
+    let previous = GUARDIANS.with(|guardians| guardians.borrow_mut().insert(caller, config));
+    touch_activity(caller);
+    
+    record_audit(caller, AuditAction::GuardiansUpdated, AuditTarget::Principal(caller), audit_json(&previous), after);
+    
+    Ok(())
+}
+
+#[update]
+fn clear_guardians() -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    if active_recovery_for(&caller).is_some() {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let removed = GUARDIANS.with(|guardians| guardians.borrow_mut().remove(&caller))
+        .ok_or(LandRegistryError::RequestNotFound)?;
+    LAST_ACTIVITY.with(|activity| activity.borrow_mut().remove(&caller));
+    
+    record_audit(caller, AuditAction::GuardiansUpdated, AuditTarget::Principal(caller), audit_json(&removed), None);
+    
+    Ok(())
+}
+
+// Lets an owner reset the dead-man switch without otherwise using the registry
+#[update]
+fn confirm_activity() -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let has_guardians = GUARDIANS.with(|guardians| guardians.borrow().contains_key(&caller));
+    if !has_guardians {
+        return Err(LandRegistryError::RequestNotFound);
+    }
+    
+    Ok(())
+}
+
+// A guardian proposes moving every parcel of an inactive owner to `new_owner`, which may be
+// the guardian themselves or someone else; their own approval counts
+#[update]
+fn start_recovery(owner: Principal, new_owner: Principal) -> LandRegistryResult<RecoveryRequestId> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Transfers)?;
+    
+    let config = GUARDIANS.with(|guardians| guardians.borrow().get(&owner).cloned())
+        .ok_or(LandRegistryError::RequestNotFound)?;
+    
+    if !config.guardians.contains(&caller) {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    if new_owner == owner || new_owner == Principal::anonymous() {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let now = time();
+    if !owner_inactive(&config, now) {
+        return Err(LandRegistryError::OwnerActive);
+    }
+    if active_recovery_for(&owner).is_some() {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let request_id = NEXT_RECOVERY_REQUEST_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    let mut request = RecoveryRequest {
+        id: request_id,
+        owner,
+        new_owner,
+        approvals: vec![caller],
+        required_approvals: config.threshold,
+        status: RecoveryStatus::Pending,
+        started_at: now,
+        executable_after: None,
+        resolved_at: None,
+        recovered_lands: Vec::new(),
+    };
+    if request.approvals.len() as u32 >= request.required_approvals {
+        request.status = RecoveryStatus::Challenge;
+        request.executable_after = Some(now + config.challenge_period_ns);
+    }
+    
+    record_audit(caller, AuditAction::RecoveryStarted, AuditTarget::RecoveryRequest(request_id), None, audit_json(&request));
+    
+    RECOVERY_REQUESTS.with(|requests| {
+        requests.borrow_mut().insert(request_id, request);
+    });
+    
+    schedule_next_timer();
+    
+    Ok(request_id)
+}
+
+#[update]
+fn approve_recovery(request_id: RecoveryRequestId) -> LandRegistryResult<RecoveryStatus> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Transfers)?;
+    let now = time();
+    
+    let request = RECOVERY_REQUESTS.with(|requests| requests.borrow().get(&request_id).cloned())
+        .ok_or(LandRegistryError::RequestNotFound)?;
+    let config = GUARDIANS.with(|guardians| guardians.borrow().get(&request.owner).cloned())
+        .ok_or(LandRegistryError::RequestNotFound)?;
+    
+    if !config.guardians.contains(&caller) {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    if request.status != RecoveryStatus::Pending || request.approvals.contains(&caller) {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    // The owner may have come back while approvals were being collected
+    if !owner_inactive(&config, now) {
+        return Err(LandRegistryError::OwnerActive);
+    }
+    
+    let status = RECOVERY_REQUESTS.with(|requests| {
+        let mut requests = requests.borrow_mut();
+        let request = requests.get_mut(&request_id)?;
+        request.approvals.push(caller);
+        if request.approvals.len() as u32 >= request.required_approvals {
+            request.status = RecoveryStatus::Challenge;
+            request.executable_after = Some(now + config.challenge_period_ns);
+        }
+        Some(request.status.clone())
+    }).ok_or(LandRegistryError::RequestNotFound)?;
+    
+    record_audit(caller, AuditAction::RecoveryApproved, AuditTarget::RecoveryRequest(request_id), None, audit_json(&status));
+    
+    schedule_next_timer();
+    
+    Ok(status)
+}
+
+// The owner can cancel at any point before execution; a guardian may withdraw a request
+// that is still collecting approvals
+#[update]
+fn cancel_recovery(request_id: RecoveryRequestId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    RECOVERY_REQUESTS.with(|requests| {
+        let mut requests = requests.borrow_mut();
+        let request = requests.get_mut(&request_id).ok_or(LandRegistryError::RequestNotFound)?;
+        
+        let is_guardian = GUARDIANS.with(|guardians| {
+            guardians.borrow().get(&request.owner)
+                .map(|config| config.guardians.contains(&caller))
+                .unwrap_or(false)
+        });
+        let allowed = match request.status {
+            RecoveryStatus::Pending => caller == request.owner || is_guardian,
+            RecoveryStatus::Challenge => caller == request.owner,
+            RecoveryStatus::Executed | RecoveryStatus::Cancelled => return Err(LandRegistryError::InvalidInput),
+        };
+        if !allowed {
+            return Err(LandRegistryError::Unauthorized);
+        }
+        
+        request.status = RecoveryStatus::Cancelled;
+        request.resolved_at = Some(time());
+        Ok(())
+    })?;
+    
+    record_audit(caller, AuditAction::RecoveryCancelled, AuditTarget::RecoveryRequest(request_id), None, None);
+    
+    schedule_next_timer();
+    
+    Ok(())
+}
+
+// Moves the owner's parcels once the challenge window has passed. Parcels under a hold stay
+// with the owner, since a hold blocks every transfer. Nothing runs while transfers are
+// paused, and a request is cancelled if the owner has been active since it was started.
+fn execute_due_recoveries() {
+    if ensure_not_paused(PauseScope::Transfers).is_err() {
+        return;
+    }
+    
+    let now = time();
+    let mut due: Vec<RecoveryRequest> = RECOVERY_REQUESTS.with(|requests| {
+        requests.borrow().values()
+            .filter(|request| request.status == RecoveryStatus::Challenge)
+            .filter(|request| request.executable_after.map(|at| at <= now).unwrap_or(false))
+            .cloned()
+            .collect()
+    });
+    due.sort_by_key(|request| request.id);
+    
+    let mut certified_changes = false;
+    for request in due {
+        let owner_returned = LAST_ACTIVITY.with(|activity| {
+            activity.borrow().get(&request.owner).map(|at| *at > request.started_at).unwrap_or(false)
+        });
+        if owner_returned {
+            RECOVERY_REQUESTS.with(|requests| {
+                if let Some(stored) = requests.borrow_mut().get_mut(&request.id) {
+                    stored.status = RecoveryStatus::Cancelled;
+                    stored.resolved_at = Some(now);
+                }
+            });
+            record_audit(system::id(), AuditAction::RecoveryCancelled, AuditTarget::RecoveryRequest(request.id), None, None);
+            continue;
+        }
+        
+        let land_ids: Vec<LandId> = OWNERSHIP_INDEX.with(|index| {
+            index.borrow().get(&request.owner)
+                .map(|ids| {
+                    let mut ids: Vec<LandId> = ids.iter().copied().collect();
+                    ids.sort();
+                    ids
+                })
+                .unwrap_or_default()
+        });
+        
+        let mut recovered = Vec::new();
+        for land_id in land_ids {
+            let held = LAND_STORAGE.with(|storage| {
+                storage.borrow().get(&land_id).map(|land| ensure_not_held(land).is_err()).unwrap_or(true)
+            });
+            if held {
+                continue;
+            }
+            
+            if let Some((previous, updated)) = reassign_land(land_id, request.new_owner) {
+                let transaction = TransactionRecord {
+                    land_id,
+                    from: previous.owner,
+                    to: request.new_owner,
+                    price: None,
+                    transaction_type: TransactionType::Recovery { request_id: request.id },
+                    timestamp: now,
+                };
+                
+                TRANSACTION_HISTORY.with(|history| {
+                    history.borrow_mut().push(transaction);
+                });
+                
+                record_audit(
+                    system::id(), AuditAction::LandTransferred, AuditTarget::Land(land_id),
+                    audit_json(&previous), audit_json(&updated),
+                );
+                certify_land_entries(land_id);
+                certified_changes = true;
+                recovered.push(land_id);
+            }
+        }
+        
+        record_audit(
+            system::id(), AuditAction::RecoveryExecuted, AuditTarget::RecoveryRequest(request.id),
+            None, audit_json(&recovered),
+        );
+        
+        RECOVERY_REQUESTS.with(|requests| {
+            if let Some(stored) = requests.borrow_mut().get_mut(&request.id) {
+                stored.status = RecoveryStatus::Executed;
+                stored.resolved_at = Some(now);
+                stored.recovered_lands = recovered;
+            }
+        });
+    }
+    
+    if certified_changes {
+        publish_certified_root();
+    }
+}
+
+#[query]
+fn get_guardians(owner: Principal) -> Option<GuardianConfig> {
+    GUARDIANS.with(|guardians| guardians.borrow().get(&owner).cloned())
+}
+
+#[query]
+fn get_recovery_request(request_id: RecoveryRequestId) -> Option<RecoveryRequest> {
+    RECOVERY_REQUESTS.with(|requests| requests.borrow().get(&request_id).cloned())
+}
+
+#[query]
+fn get_recovery_requests_for_owner(owner: Principal, page: PageRequest) -> Page<RecoveryRequest> {
+    RECOVERY_REQUESTS.with(|requests| {
+        id_page(&requests.borrow(), &page, |request| request.owner == owner, RecoveryRequest::clone)
+    })
+}
+
+// Audit log functions
+fn audit_json<T: Serialize>(value: &T) -> Option<String> {
+    serde_json::to_string(value).ok()
+}
+
+fn record_audit(
+    actor: Principal,
+    action: AuditAction,
+    target: AuditTarget,
+    before: Option<String>,
+    after: Option<String>,
+) {
+    AUDIT_LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        let id = log.len() as AuditEntryId;
+        log.push(AuditEntry {
+            id,
+            actor,
+            action,
+            target,
+            before,
+            after,
+            timestamp: time(),
+        });
+    });
+}
+
+fn audit_entry_matches(entry: &AuditEntry, filter: &AuditFilter) -> bool {
+    filter.actor.map(|actor| entry.actor == actor).unwrap_or(true) &&
+    filter.action.as_ref().map(|action| entry.action == *action).unwrap_or(true) &&
+    filter.target.as_ref().map(|target| entry.target == *target).unwrap_or(true) &&
+    filter.from.map(|from| entry.timestamp >= from).unwrap_or(true) &&
+    filter.to.map(|to| entry.timestamp <= to).unwrap_or(true)
+}
+
+// Entries are always ordered by id, the order they were recorded in this canister;
+// history brought in by a snapshot import follows the entries that were already here
+#[query]
+fn get_audit_log(filter: AuditFilter, page: PageRequest) -> LandRegistryResult<Page<AuditEntry>> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::SupportAgent, Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    Ok(AUDIT_LOG.with(|log| {
+        position_page(&log.borrow(), &page, |entry| audit_entry_matches(entry, &filter), AuditEntry::clone)
+    }))
+}
+
+// Circuit breaker functions
+//
+// A scope blocks the user-facing updates in its area; All blocks every user-facing update.
+// Withdrawals (delisting, cancelling a rezoning request) are never blocked, and admin
+// endpoints stay available so an incident can be repaired while paused.
+fn ensure_not_paused(scope: PauseScope) -> LandRegistryResult<()> {
+    let paused = PAUSES.with(|pauses| {
+        let pauses = pauses.borrow();
+        pauses.contains_key(&PauseScope::All) || pauses.contains_key(&scope)
+    });
+    if paused {
+        Err(LandRegistryError::Paused)
+    } else {
+        Ok(())
+    }
+}
+
+#[update]
+fn pause(scope: PauseScope, reason: String) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if reason.trim().is_empty() {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let state = PauseState {
+        scope,
+        reason,
+        paused_at: time(),
+        paused_by: caller,
+    };
+    record_audit(caller, AuditAction::Paused, AuditTarget::Pause(scope), None, audit_json(&state));
+    
+    PAUSES.with(|pauses| {
+        pauses.borrow_mut().insert(scope, state);
+    });
+    
+    Ok(())
+}
+
+#[update]
+fn unpause(scope: PauseScope) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let removed = PAUSES.with(|pauses| pauses.borrow_mut().remove(&scope))
+        .ok_or(LandRegistryError::InvalidInput)?;
+    record_audit(caller, AuditAction::Unpaused, AuditTarget::Pause(scope), audit_json(&removed), None);
+    
+    run_deferred_admin_proposals();
+    schedule_next_timer();
+    
+    Ok(())
+}
+
+#[query]
+fn get_pause_status() -> Vec<PauseState> {
+    let mut pauses: Vec<PauseState> = PAUSES.with(|pauses| pauses.borrow().values().cloned().collect());
+    pauses.sort_by_key(|pause| pause.paused_at);
+    pauses
+}
+
+// Admin functions
+#[update]
+fn add_admin(new_admin: Principal) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if required_approvals() > 1 {
+        return Err(LandRegistryError::ApprovalRequired);
+    }
+    
+    // Kept for existing clients; an admin is a SuperAdmin
+    assign_role(new_admin, Role::SuperAdmin, caller);
+    
+    Ok(())
+}
+
+// Kept for existing clients, with its original meaning: only SuperAdmins are admins
+#[query]
+fn is_admin(user: Principal) -> bool {
+    has_role(&user, &[Role::SuperAdmin])
+}
+
+#[update]
+fn remove_land(land_id: LandId, reason: String) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if required_approvals() > 1 {
+        return Err(LandRegistryError::ApprovalRequired);
+    }
+    
+    tombstone_land(land_id, reason, caller)
+}
+
+// Removal is soft: the parcel leaves every store and index but is kept as a tombstone
+// until TOMBSTONE_RETENTION_NS has passed. Its footprint is freed for new registrations.
+fn tombstone_land(land_id: LandId, reason: String, actor: Principal) -> LandRegistryResult<()> {
+    if reason.trim().is_empty() {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let land = remove_land_record(land_id).ok_or(LandRegistryError::LandNotFound)?;
+    clear_operators(land_id);
+    cancel_pending_rezoning(land_id, actor, &format!("Parcel removed: {}", reason));
+    
+    let now = time();
+    let transaction = TransactionRecord {
+        land_id,
+        from: land.owner,
+        to: Principal::anonymous(),
+        price: None,
+        transaction_type: TransactionType::Removal { reason: reason.clone() },
+        timestamp: now,
+    };
+    
+    TRANSACTION_HISTORY.with(|history| {
+        history.borrow_mut().push(transaction);
+    });
+    
+    record_audit(actor, AuditAction::LandRemoved, AuditTarget::Land(land_id), audit_json(&land), Some(reason.clone()));
+    
+    TOMBSTONES.with(|tombstones| {
+        tombstones.borrow_mut().insert(land_id, Tombstone {
+            land,
+            reason,
+            removed_by: actor,
+            removed_at: now,
+            purge_after: now + TOMBSTONE_RETENTION_NS,
+        });
+    });
+    
+    certify_land(land_id);
+    schedule_next_timer();
+    
+    Ok(())
+}
+
+// Brings a tombstoned parcel back to its last owner; the listing it had is not restored
+#[update]
+fn restore_land(land_id: LandId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    // A tombstone past its retention is as good as purged, even if the timer has not run yet
+    let now = time();
+    let tombstone = TOMBSTONES.with(|tombstones| {
+        tombstones.borrow().get(&land_id).cloned()
+    })
+    .filter(|tombstone| tombstone.purge_after > now)
+    .ok_or(LandRegistryError::LandNotFound)?;
+    let land = tombstone.land;
+    
+    // The footprint may have been registered again in the meantime
+    if check_land_overlap(&land.coordinates, &land.dimensions) {
+        return Err(LandRegistryError::LandAlreadyExists);
+    }
+    
+    TOMBSTONES.with(|tombstones| tombstones.borrow_mut().remove(&land_id));
+    insert_land(land.clone());
+    
+    let transaction = TransactionRecord {
+        land_id,
+        from: Principal::anonymous(),
+        to: land.owner,
+        price: None,
+        transaction_type: TransactionType::Restoration,
+        timestamp: now,
+    };
+    
+    TRANSACTION_HISTORY.with(|history| {
+        history.borrow_mut().push(transaction);
+    });
+    
+    record_audit(caller, AuditAction::LandRestored, AuditTarget::Land(land_id), None, audit_json(&land));
+    
+    certify_land(land_id);
+    schedule_next_timer();
+    
+    Ok(())
+}
+
+fn purge_expired_tombstones() {
+    let now = time();
+    let expired: Vec<Tombstone> = TOMBSTONES.with(|tombstones| {
+        let mut tombstones = tombstones.borrow_mut();
+        let ids: Vec<LandId> = tombstones.values()
+            .filter(|tombstone| tombstone.purge_after <= now)
+            .map(|tombstone| tombstone.land.id)
+            .collect();
+        ids.iter().filter_map(|id| tombstones.remove(id)).collect()
+    });
+    
+    for tombstone in expired {
+        record_audit(
+            system::id(), AuditAction::LandPurged, AuditTarget::Land(tombstone.land.id),
+            audit_json(&tombstone.land), None,
+        );
+    }
+}
+
+#[query]
+fn get_removed_lands(page: PageRequest) -> LandRegistryResult<Page<Tombstone>> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::Moderator, Role::SupportAgent]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    // Ordered by land id
+    Ok(TOMBSTONES.with(|tombstones| id_page(&tombstones.borrow(), &page, |_| true, Tombstone::clone)))
+}
+
+// District and zoning functions
+#[update]
+fn create_district(definition: DistrictDefinition) -> LandRegistryResult<DistrictId> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::ZoningOfficer]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    validate_district_definition(&definition, None)?;
+    
+    let district_id = NEXT_DISTRICT_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    let now = time();
+    let district = District {
+        id: district_id,
+        name: definition.name,
+        min_coords: definition.min_coords,
+        max_coords: definition.max_coords,
+        zoning: definition.zoning,
+        created_at: now,
+        last_updated: now,
+    };
+    
+    record_audit(caller, AuditAction::DistrictCreated, AuditTarget::District(district_id), None, audit_json(&district));
+    
+    DISTRICTS.with(|districts| {
+        districts.borrow_mut().insert(district_id, district);
+    });
+    
+    Ok(district_id)
+}
+
+// Existing parcels are not re-checked; new rules apply to later registrations and type changes
+#[update]
+fn update_district(district_id: DistrictId, definition: DistrictDefinition) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::ZoningOfficer]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let exists = DISTRICTS.with(|districts| districts.borrow().contains_key(&district_id));
+    if !exists {
+        return Err(LandRegistryError::DistrictNotFound);
+    }
+    
+    validate_district_definition(&definition, Some(district_id))?;
+    
+    let (previous, updated) = DISTRICTS.with(|districts| {
+        let mut districts = districts.borrow_mut();
+        let district = districts.get_mut(&district_id)?;
+        let previous = district.clone();
+        district.name = definition.name;
+        district.min_coords = definition.min_coords;
+        district.max_coords = definition.max_coords;
+        district.zoning = definition.zoning;
+        district.last_updated = time();
+        Some((previous, district.clone()))
+    }).ok_or(LandRegistryError::DistrictNotFound)?;
+    
+    record_audit(caller, AuditAction::DistrictUpdated, AuditTarget::District(district_id), audit_json(&previous), audit_json(&updated));
+    
+    Ok(())
+}
+
+#[update]
+fn remove_district(district_id: DistrictId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::ZoningOfficer]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let removed = DISTRICTS.with(|districts| {
+        districts.borrow_mut().remove(&district_id)
+    }).ok_or(LandRegistryError::DistrictNotFound)?;
+    
+    record_audit(caller, AuditAction::DistrictRemoved, AuditTarget::District(district_id), audit_json(&removed), None);
+    
+    Ok(())
+}
+
+#[query]
+fn get_districts(page: PageRequest) -> Page<District> {
+    DISTRICTS.with(|districts| id_page(&districts.borrow(), &page, |_| true, District::clone))
+}
+
+#[query]
+fn get_district(district_id: DistrictId) -> Option<District> {
+    DISTRICTS.with(|districts| {
+        districts.borrow().get(&district_id).cloned()
+    })
+}
+
+// The district of a parcel is the one containing its origin
+#[query]
+fn get_land_district(land_id: LandId) -> Option<District> {
+    let land = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
+    })?;
+    
+    DISTRICTS.with(|districts| {
+        districts.borrow().values()
+            .find(|district| district_contains_point(district, &land.coordinates))
+            .cloned()
+    })
+}
+
+// Attribute schema functions
+// Changing a schema does not revalidate parcels that already carry the attribute
+#[update]
+fn define_attribute(schema: AttributeSchema) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if schema.key.trim().is_empty() || schema.label.trim().is_empty() {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let valid_kind = match &schema.kind {
+        AttributeKind::Enum(options) => !options.is_empty(),
+        AttributeKind::Number { min: Some(min), max: Some(max) } => min <= max,
+        _ => true,
+    };
+    if !valid_kind {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let after = audit_json(&schema);
+    let key = schema.key.clone();
+    let previous = ATTRIBUTE_SCHEMAS.with(|schemas| {
+        schemas.borrow_mut().insert(schema.key.clone(), schema)
+    });
+    
+    record_audit(caller, AuditAction::AttributeDefined, AuditTarget::Attribute(key), audit_json(&previous), after);
+    
+    Ok(())
+}
+
+#[update]
+fn remove_attribute_schema(key: String) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let removed = ATTRIBUTE_SCHEMAS.with(|schemas| {
+        schemas.borrow_mut().remove(&key)
+    }).ok_or(LandRegistryError::InvalidAttribute)?;
+    
+    record_audit(caller, AuditAction::AttributeRemoved, AuditTarget::Attribute(key), audit_json(&removed), None);
+    
+    Ok(())
+}
+
+#[query]
+fn get_attribute_schemas() -> Vec<AttributeSchema> {
+    let mut schemas: Vec<AttributeSchema> = ATTRIBUTE_SCHEMAS.with(|schemas| {
+        schemas.borrow().values().cloned().collect()
+    });
+    schemas.sort_by(|a, b| a.key.cmp(&b.key));
+    schemas
+}
+
+// Rezoning functions
+#[update]
+fn request_land_type_change(
+    land_id: LandId,
+    new_type: LandType,
+    reason: String,
+) -> LandRegistryResult<RezoningRequestId> {
+    let caller = active_caller();
+    ensure_not_paused(PauseScope::Registration)?;
+    
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    if land_info.owner != caller {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    ensure_not_held(&land_info)?;
+    
+    if reason.trim().is_empty() ||
+       std::mem::discriminant(&land_info.land_type) == std::mem::discriminant(&new_type) {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    // Only one open request per parcel
+    let has_pending = REZONING_REQUESTS.with(|requests| {
+        requests.borrow().values()
+            .any(|request| request.land_id == land_id && request.status == RezoningStatus::Pending)
+    });
+    if has_pending {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    // Fail early rather than queue a request that can never be approved
+    check_zoning(&land_info.coordinates, &land_info.dimensions, &new_type)?;
+    
+    let request_id = NEXT_REZONING_REQUEST_ID.with(|id| {
+        let current_id = *id.borrow();
+        *id.borrow_mut() = current_id + 1;
+        current_id
+    });
+    
+    let request = RezoningRequest {
+        id: request_id,
+        land_id,
+        requester: caller,
+        current_type: land_info.land_type,
+        new_type,
+        reason,
+        status: RezoningStatus::Pending,
+        created_at: time(),
+        resolved_at: None,
+        resolved_by: None,
+        resolution_note: None,
+    };
+    
+    record_audit(caller, AuditAction::RezoningRequested, AuditTarget::RezoningRequest(request_id), None, audit_json(&request));
+    
+    REZONING_REQUESTS.with(|requests| {
+        requests.borrow_mut().insert(request_id, request);
+    });
+    
+    Ok(request_id)
+}
+
+#[update]
+fn cancel_rezoning_request(request_id: RezoningRequestId) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    
+    let request = REZONING_REQUESTS.with(|requests| {
+        requests.borrow().get(&request_id).cloned()
+    }).ok_or(LandRegistryError::RequestNotFound)?;
+    
+    if request.requester != caller {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    resolve_rezoning_request(request_id, RezoningStatus::Cancelled, caller, None)
+}
+
+#[update]
+fn approve_rezoning_request(request_id: RezoningRequestId, note: Option<String>) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::ZoningOfficer]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    apply_rezoning(request_id, caller, note)
+}
+
+#[update]
+fn reject_rezoning_request(request_id: RezoningRequestId, note: Option<String>) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::ZoningOfficer]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    resolve_rezoning_request(request_id, RezoningStatus::Rejected, caller, note)
+}
+
+fn resolve_rezoning_request(
+    request_id: RezoningRequestId,
+    status: RezoningStatus,
+    resolver: Principal,
+    note: Option<String>,
+) -> LandRegistryResult<()> {
+    REZONING_REQUESTS.with(|requests| {
+        let mut requests = requests.borrow_mut();
+        let request = requests.get_mut(&request_id)
+            .ok_or(LandRegistryError::RequestNotFound)?;
+        
+        if request.status != RezoningStatus::Pending {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        
+        let previous = audit_json(request);
+        request.status = status;
+        request.resolved_at = Some(time());
+        request.resolved_by = Some(resolver);
+        request.resolution_note = note;
+        Ok((previous, audit_json(request)))
+    }).map(|(previous, updated)| {
+        record_audit(resolver, AuditAction::RezoningResolved, AuditTarget::RezoningRequest(request_id), previous, updated);
+    })
+}
+
+// A request belongs to the owner who filed it, so it lapses when the parcel changes hands
+// or is removed; otherwise it could never be approved and would block the next owner
+fn cancel_pending_rezoning(land_id: LandId, actor: Principal, reason: &str) {
+    let pending: Vec<RezoningRequestId> = REZONING_REQUESTS.with(|requests| {
+        requests.borrow().values()
+            .filter(|request| request.land_id == land_id && request.status == RezoningStatus::Pending)
+            .map(|request| request.id)
+            .collect()
+    });
+    
+    for request_id in pending {
+        let _ = resolve_rezoning_request(
+            request_id,
+            RezoningStatus::Cancelled,
+            actor,
+            Some(reason.to_string()),
+        );
+    }
+}
+
+// Shared by ZoningOfficer approval and a passed ApproveRezoning governance vote
+fn apply_rezoning(
+    request_id: RezoningRequestId,
+    approver: Principal,
+    note: Option<String>,
+) -> LandRegistryResult<()> {
+    let request = REZONING_REQUESTS.with(|requests| {
+        requests.borrow().get(&request_id).cloned()
+    }).ok_or(LandRegistryError::RequestNotFound)?;
+    
+    if request.status != RezoningStatus::Pending {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    let land_info = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&request.land_id).cloned()
+    }).ok_or(LandRegistryError::LandNotFound)?;
+    
+    // The parcel changed hands since the request was filed
+    if land_info.owner != request.requester {
+        return Err(LandRegistryError::OwnershipError);
+    }
+    
+    // A frozen or disputed parcel keeps its current type until the hold is lifted
+    ensure_not_held(&land_info)?;
+    
+    // Zoning may have changed while the request was pending
+    check_zoning(&land_info.coordinates, &land_info.dimensions, &request.new_type)?;
+    
+    let now = time();
+    let updated = LAND_STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let land = storage.get_mut(&request.land_id)?;
+        land.land_type = request.new_type.clone();
+        land.last_updated = now;
+        Some(land.clone())
+    });
+    record_audit(approver, AuditAction::LandRezoned, AuditTarget::Land(request.land_id), audit_json(&land_info), audit_json(&updated));
+    
+    // Keep the type indexes in step with the parcel
+    if let Some(updated) = updated {
+        unindex_land_type(updated.id);
+        index_land_type(&updated);
+        
+        if let Some(listing) = remove_listing(updated.id) {
+            insert_listing(listing);
+        }
+    }
+    
+    resolve_rezoning_request(request_id, RezoningStatus::Approved, approver, note)?;
+    
+    let transaction = TransactionRecord {
+        land_id: request.land_id,
+        from: land_info.owner,
+        to: land_info.owner,
+        price: None,
+        transaction_type: TransactionType::Rezoning,
+        timestamp: now,
+    };
+    
+    TRANSACTION_HISTORY.with(|history| {
+        history.borrow_mut().push(transaction);
+    });
+    
+    Ok(())
+}
+
+#[query]
+fn get_rezoning_request(request_id: RezoningRequestId) -> Option<RezoningRequest> {
+    REZONING_REQUESTS.with(|requests| {
+        requests.borrow().get(&request_id).cloned()
+    })
+}
+
+#[query]
+fn get_pending_rezoning_requests(page: PageRequest) -> Page<RezoningRequest> {
+    REZONING_REQUESTS.with(|requests| {
+        id_page(&requests.borrow(), &page, |request| request.status == RezoningStatus::Pending, RezoningRequest::clone)
+    })
+}
+
+#[query]
+fn get_rezoning_requests_for_land(land_id: LandId, page: PageRequest) -> Page<RezoningRequest> {
+    REZONING_REQUESTS.with(|requests| {
+        id_page(&requests.borrow(), &page, |request| request.land_id == land_id, RezoningRequest::clone)
+    })
+}
+
+// Export functions for GIS and 3D tooling
+fn land_type_name(land_type: &LandType) -> &'static str {
+    match land_type {
+        LandType::Residential => "Residential",
+        LandType::Commercial => "Commercial",
+        LandType::Industrial => "Industrial",
+        LandType::Agricultural => "Agricultural",
+        LandType::Entertainment => "Entertainment",
+        LandType::Mixed => "Mixed",
+    }
+}
+
+fn land_type_color(land_type: &LandType) -> [f32; 4] {
+    match land_type {
+        LandType::Residential => [0.30, 0.69, 0.31, 1.0],
+        LandType::Commercial => [0.13, 0.59, 0.95, 1.0],
+        LandType::Industrial => [0.62, 0.62, 0.62, 1.0],
+        LandType::Agricultural => [0.80, 0.86, 0.22, 1.0],
+        LandType::Entertainment => [0.91, 0.12, 0.39, 1.0],
+        LandType::Mixed => [1.00, 0.60, 0.00, 1.0],
+    }
+}
+
+const ALL_LAND_TYPES: [LandType; 6] = [
+    LandType::Residential,
+    LandType::Commercial,
+    LandType::Industrial,
+    LandType::Agricultural,
+    LandType::Entertainment,
+    LandType::Mixed,
+];
+
+fn exported_lands(filters: &Option<SearchFilters>, page: &PageRequest) -> Page<LandInfo> {
+    let lands: Vec<LandInfo> = match filters {
+        Some(filters) => run_search(filters).into_iter().map(|(land, _)| land).collect(),
+        None => LAND_STORAGE.with(|storage| storage.borrow().values().cloned().collect()),
+    };
+    let page = PageRequest {
+        limit: Some(page.limit.unwrap_or(MAX_EXPORT_PAGE_LIMIT).min(MAX_EXPORT_PAGE_LIMIT)),
+        ..page.clone()
+    };
+    land_page(lands, &page)
+}
+
+fn land_feature(land: &LandInfo, listing_price: Option<Price>) -> serde_json::Value {
+    let (x, y) = (land.coordinates.x as i64, land.coordinates.y as i64);
+    let (w, h) = (land.dimensions.width as i64, land.dimensions.height as i64);
+    let metadata = land.metadata.as_ref();
+    
+    serde_json::json!({
+        "type": "Feature",
+        "id": land.id,
+        "geometry": {
+            "type": "Polygon",
+            "coordinates": [[[x, y], [x + w, y], [x + w, y + h], [x, y + h], [x, y]]],
+        },
+        "properties": {
+            "id": land.id,
+            "owner": land.owner.to_text(),
+            "land_type": land_type_name(&land.land_type),
+            "description": land.description,
+            "z": land.coordinates.z,
+            "width": land.dimensions.width,
+            "height": land.dimensions.height,
+            "depth": land.dimensions.depth,
+            "area": calculate_land_area(&land.dimensions),
+            "environment": metadata.and_then(|m| m.environment.clone()),
+            "special_features": metadata.map(|m| m.special_features.clone()).unwrap_or_default(),
+            "access_roads": metadata.map(|m| m.access_roads.clone()).unwrap_or_default(),
+            "utilities": metadata.map(|m| m.utilities.clone()).unwrap_or_default(),
+            "attributes": land_attributes(land).iter()
+                .map(|attribute| (attribute.key.clone(), attribute_display(&attribute.value)))
+                .collect::<BTreeMap<String, String>>(),
+            "for_sale": listing_price.is_some(),
+            "price": listing_price,
+            "created_at": land.created_at,
+            "last_updated": land.last_updated,
+        },
+    })
+}
+
+// Positions are raw world units on the x/y plane, not WGS84
+fn render_geojson(lands: &[LandInfo]) -> String {
+    let features: Vec<serde_json::Value> = MARKETPLACE.with(|marketplace| {
+        let marketplace = marketplace.borrow();
+        lands.iter()
+            .map(|land| land_feature(land, marketplace.get(&land.id).map(|listing| listing.price)))
+            .collect()
+    });
+    
+    serde_json::json!({
+        "type": "FeatureCollection",
+        "features": features,
+    }).to_string()
+}
+
+// Unit cube with per-face normals: 24 vertices, 36 indices
+fn unit_cube_buffer() -> Vec<u8> {
+    let faces: [([f32; 3], [[f32; 3]; 4]); 6] = [
+        ([1.0, 0.0, 0.0], [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [1.0, 1.0, 1.0], [1.0, 0.0, 1.0]]),
+        ([-1.0, 0.0, 0.0], [[0.0, 0.0, 1.0], [0.0, 1.0, 1.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]]),
+        ([0.0, 1.0, 0.0], [[0.0, 1.0, 0.0], [0.0, 1.0, 1.0], [1.0, 1.0, 1.0], [1.0, 1.0, 0.0]]),
+        ([0.0, -1.0, 0.0], [[0.0, 0.0, 1.0], [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 0.0, 1.0]]),
+        ([0.0, 0.0, 1.0], [[0.0, 0.0, 1.0], [1.0, 0.0, 1.0], [1.0, 1.0, 1.0], [0.0, 1.0, 1.0]]),
+        ([0.0, 0.0, -1.0], [[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]]),
+    ];
+    
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut indices = Vec::new();
+    for (face, (normal, corners)) in faces.iter().enumerate() {
+        for corner in corners {
+            positions.extend(corner.iter().flat_map(|v| v.to_le_bytes()));
+            normals.extend(normal.iter().flat_map(|v| v.to_le_bytes()));
+        }
+        let base = (face * 4) as u16;
+        for offset in [0, 1, 2, 0, 2, 3] {
+            indices.extend((base + offset).to_le_bytes());
+        }
+    }
+    
+    let mut buffer = positions;
+    buffer.extend(normals);
+    buffer.extend(indices);
+    buffer
+}
+
+// Binary glTF (GLB) scene with one box per parcel, world z mapped to the glTF up axis
+fn render_gltf(lands: &[LandInfo]) -> Vec<u8> {
+    let buffer = unit_cube_buffer();
+    
+    let materials: Vec<serde_json::Value> = ALL_LAND_TYPES.iter()
+        .map(|land_type| serde_json::json!({
+            "name": land_type_name(land_type),
+            "pbrMetallicRoughness": {
+                "baseColorFactor": land_type_color(land_type),
+                "metallicFactor": 0.0,
+                "roughnessFactor": 0.9,
+            },
+        }))
+        .collect();
+    
+    let meshes: Vec<serde_json::Value> = (0..ALL_LAND_TYPES.len())
+        .map(|material| serde_json::json!({
+            "name": land_type_name(&ALL_LAND_TYPES[material]),
+            "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1 },
+                "indices": 2,
+                "material": material,
+            }],
+        }))
+        .collect();
+    
+    let nodes: Vec<serde_json::Value> = lands.iter()
+        .map(|land| {
+            let mesh = ALL_LAND_TYPES.iter()
+                .position(|t| std::mem::discriminant(t) == std::mem::discriminant(&land.land_type))
+                .unwrap_or(0);
+            serde_json::json!({
+                "name": format!("land-{}", land.id),
+                "mesh": mesh,
+                "translation": [
+                    land.coordinates.x as f64,
+                    land.coordinates.z as f64,
+                    -(land.coordinates.y as f64 + land.dimensions.height as f64),
+                ],
+                "scale": [land.dimensions.width, land.dimensions.depth, land.dimensions.height],
+                "extras": {
+                    "land_id": land.id,
+                    "land_type": land_type_name(&land.land_type),
+                    "owner": land.owner.to_text(),
+                },
+            })
+        })
+        .collect();
+    
+    let document = serde_json::json!({
+        "asset": { "version": "2.0", "generator": "virtual_land_registry_backend" },
+        "scene": 0,
+        "scenes": [{ "nodes": (0..nodes.len()).collect::<Vec<_>>() }],
+        "nodes": nodes,
+        "meshes": meshes,
+        "materials": materials,
+        "buffers": [{ "byteLength": buffer.len() }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 288, "target": 34962 },
+            { "buffer": 0, "byteOffset": 288, "byteLength": 288, "target": 34962 },
+            { "buffer": 0, "byteOffset": 576, "byteLength": 72, "target": 34963 },
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 24, "type": "VEC3",
+              "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 1.0] },
+            { "bufferView": 1, "componentType": 5126, "count": 24, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5123, "count": 36, "type": "SCALAR" },
+        ],
+    });
+    
+    // Chunks must be 4-byte aligned
+    let mut json = document.to_string().into_bytes();
+    json.resize(json.len().next_multiple_of(4), b' ');
+    let mut bin = buffer;
+    bin.resize(bin.len().next_multiple_of(4), 0);
+    
+    let total_length = 12 + 8 + json.len() + 8 + bin.len();
+    let mut glb = Vec::with_capacity(total_length);
+    glb.extend(0x46546C67u32.to_le_bytes()); // "glTF"
+    glb.extend(2u32.to_le_bytes());
+    glb.extend((total_length as u32).to_le_bytes());
+    glb.extend((json.len() as u32).to_le_bytes());
+    glb.extend(0x4E4F534Au32.to_le_bytes()); // "JSON"
+    glb.extend(json);
+    glb.extend((bin.len() as u32).to_le_bytes());
+    glb.extend(0x004E4942u32.to_le_bytes()); // "BIN\0"
+    glb.extend(bin);
+    glb
+}
+
+#[query]
+fn export_geojson(filters: Option<SearchFilters>, page: PageRequest) -> GeoJsonExport {
+    let lands = exported_lands(&filters, &page);
+    GeoJsonExport {
+        geojson: render_geojson(&lands.items),
+        next_cursor: lands.next_cursor,
+        total_count: lands.total_count,
+    }
+}
+
+#[query]
+fn export_gltf(filters: Option<SearchFilters>, page: PageRequest) -> GltfExport {
+    let lands = exported_lands(&filters, &page);
+    GltfExport {
+        glb: render_gltf(&lands.items),
+        next_cursor: lands.next_cursor,
+        total_count: lands.total_count,
+    }
+}
+
+// HTTP gateway
+fn parse_land_type(name: &str) -> Option<LandType> {
+    ALL_LAND_TYPES.iter()
+        .find(|land_type| land_type_name(land_type).eq_ignore_ascii_case(name))
+        .cloned()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn parse_query_string(query: &str) -> HashMap<String, String> {
+    query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn http_response(status_code: u16, content_type: &str, max_age: u32, body: Vec<u8>) -> HttpResponse {
+    HttpResponse {
+        status_code,
+        headers: vec![
+            ("Content-Type".to_string(), content_type.to_string()),
+            ("Content-Length".to_string(), body.len().to_string()),
+            ("Cache-Control".to_string(), format!("public, max-age={}", max_age)),
+            ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+        ],
+        body,
+    }
+}
+
+fn json_response<T: Serialize>(value: &T, max_age: u32) -> HttpResponse {
+    match serde_json::to_vec(value) {
+        Ok(body) => http_response(200, "application/json", max_age, body),
+        Err(_) => http_error(500, "serialization failed"),
+    }
+}
+
+fn http_error(status_code: u16, message: &str) -> HttpResponse {
+    let body = serde_json::json!({ "error": message }).to_string().into_bytes();
+    http_response(status_code, "application/json", 0, body)
+}
+
+// NFT-style metadata so wallets and marketplaces can render a parcel
+fn land_nft_metadata(land: &LandInfo) -> serde_json::Value {
+    let mut attributes = vec![
+        serde_json::json!({ "trait_type": "Land Type", "value": land_type_name(&land.land_type) }),
+        serde_json::json!({ "trait_type": "X", "value": land.coordinates.x, "display_type": "number" }),
+        serde_json::json!({ "trait_type": "Y", "value": land.coordinates.y, "display_type": "number" }),
+        serde_json::json!({ "trait_type": "Z", "value": land.coordinates.z, "display_type": "number" }),
+        serde_json::json!({ "trait_type": "Width", "value": land.dimensions.width, "display_type": "number" }),
+        serde_json::json!({ "trait_type": "Height", "value": land.dimensions.height, "display_type": "number" }),
+        serde_json::json!({ "trait_type": "Depth", "value": land.dimensions.depth, "display_type": "number" }),
+        serde_json::json!({ "trait_type": "Area", "value": calculate_land_area(&land.dimensions), "display_type": "number" }),
+    ];
+    
+    if let Some(ref metadata) = land.metadata {
+        if let Some(ref environment) = metadata.environment {
+            attributes.push(serde_json::json!({ "trait_type": "Environment", "value": environment }));
+        }
+        for feature in &metadata.special_features {
+            attributes.push(serde_json::json!({ "trait_type": "Special Feature", "value": feature }));
+        }
+        for road in &metadata.access_roads {
+            attributes.push(serde_json::json!({ "trait_type": "Access Road", "value": road }));
+        }
+        for utility in &metadata.utilities {
+            attributes.push(serde_json::json!({ "trait_type": "Utility", "value": utility }));
+        }
+    }
+    
+    ATTRIBUTE_SCHEMAS.with(|schemas| {
+        let schemas = schemas.borrow();
+        for attribute in land_attributes(land) {
+            let trait_type = schemas.get(&attribute.key)
+                .map(|schema| schema.label.clone())
+                .unwrap_or_else(|| attribute.key.clone());
+            let value = match attribute.value {
+                AttributeValue::Number(value) => serde_json::json!(value),
+                AttributeValue::Bool(value) => serde_json::json!(value),
+                ref other => serde_json::json!(attribute_display(other)),
+            };
+            attributes.push(serde_json::json!({ "trait_type": trait_type, "value": value }));
+        }
+    });
+    
+    serde_json::json!({
+        "name": format!("Land #{}", land.id),
+        "description": land.description,
+        "external_url": format!("/land/{}", land.id),
+        "owner": land.owner.to_text(),
+        "attributes": attributes,
+    })
+}
+
+// `cursor` is the last land id seen; results are always ordered by id
+fn http_page_request(params: &HashMap<String, String>) -> Result<PageRequest, HttpResponse> {
+    let cursor = match params.get("cursor").map(|cursor| cursor.parse::<LandId>()) {
+        Some(Ok(id)) => Some(PageCursor { sort_value: id, id }),
+        Some(Err(_)) => return Err(http_error(400, "invalid cursor")),
+        None => None,
+    };
+    
+    let limit = match params.get("limit").map(|limit| limit.parse::<u32>()) {
+        Some(Ok(limit)) => Some(limit),
+        Some(Err(_)) => return Err(http_error(400, "invalid limit")),
+        None => None,
+    };
+    
+    Ok(PageRequest { cursor, limit, sort_by: Some(SortKey::Id), direction: None })
+}
+
+// Exports are paged like /lands; the next cursor is sent in an X-Next-Cursor header
+fn http_export(query: &str, content_type: &str, render: fn(&[LandInfo]) -> Vec<u8>) -> HttpResponse {
+    let page = match http_page_request(&parse_query_string(query)) {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+    
+    let lands = exported_lands(&None, &page);
+    let mut response = http_response(200, content_type, 300, render(&lands.items));
+    if let Some(cursor) = lands.next_cursor {
+        response.headers.push(("X-Next-Cursor".to_string(), cursor.id.to_string()));
+        response.headers.push(("Access-Control-Expose-Headers".to_string(), "X-Next-Cursor".to_string()));
+    }
+    response
+}
+
+fn http_get_lands(query: &str) -> HttpResponse {
+    let params = parse_query_string(query);
+    
+    let owner = match params.get("owner").map(Principal::from_text) {
+        Some(Ok(owner)) => Some(owner),
+        Some(Err(_)) => return http_error(400, "invalid owner principal"),
+        None => None,
+    };
+    
+    let land_type = match params.get("type").map(|name| parse_land_type(name)) {
+        Some(Some(land_type)) => Some(land_type),
+        Some(None) => return http_error(400, "unknown land type"),
+        None => None,
+    };
+    
+    let page = match http_page_request(&params) {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+    
+    let lands: Vec<LandInfo> = LAND_STORAGE.with(|storage| {
+        storage.borrow().values()
+            .filter(|land| owner.map(|owner| land.owner == owner).unwrap_or(true))
+            .filter(|land| {
+                land_type.as_ref()
+                    .map(|t| std::mem::discriminant(&land.land_type) == std::mem::discriminant(t))
+                    .unwrap_or(true)
+            })
+            .cloned()
+            .collect()
+    });
+    
+    json_response(&land_page(lands, &page), 60)
+}
+
+// Responses are dynamic and carry no IC-Certificate header, so only the raw domain
+// (<canister-id>.raw.icp0.io) serves them; certified reads go through the *_certified queries
+#[query]
+fn http_request(request: HttpRequest) -> HttpResponse {
+    if !request.method.eq_ignore_ascii_case("GET") {
+        return http_error(405, "method not allowed");
+    }
+    
+    let (path, query) = request.url.split_once('?').unwrap_or((&request.url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    
+    match segments.as_slice() {
+        ["land", id] | ["land", id, "metadata.json"] => {
+            let Ok(land_id) = id.parse::<LandId>() else {
+                return http_error(400, "invalid land id");
+            };
+            let Some(land) = get_land(land_id) else {
+                return http_error(404, "land not found");
+            };
+            if segments.len() == 3 {
+                json_response(&land_nft_metadata(&land), 300)
+            } else {
+                json_response(&land, 60)
+            }
+        }
+        ["lands"] => http_get_lands(query),
+        ["lands.geojson"] => http_export(query, "application/geo+json", |lands| render_geojson(lands).into_bytes()),
+        ["lands.glb"] => http_export(query, "model/gltf-binary", render_gltf),
+        ["stats"] => json_response(&get_land_statistics(), 30),
+        _ => http_error(404, "not found"),
+    }
+}
+
+// Certified data
+//
+// The certified tree follows the IC HashTree format:
+//   lands/<land id, 8 bytes big-endian>    -> owner principal bytes
+//   listings/<land id, 8 bytes big-endian> -> price, 8 bytes big-endian
+// Both sections are red-black trees updated in place, so a change costs O(log n).
+const CERTIFIED_LANDS_LABEL: &[u8] = b"lands";
+const CERTIFIED_LISTINGS_LABEL: &[u8] = b"listings";
+
+type CertifiedTree = RbTree<Vec<u8>, RbTree<Vec<u8>, Vec<u8>>>;
+
+fn empty_certified_tree() -> CertifiedTree {
+    let mut tree = CertifiedTree::new();
+    tree.insert(CERTIFIED_LANDS_LABEL.to_vec(), RbTree::new());
+    tree.insert(CERTIFIED_LISTINGS_LABEL.to_vec(), RbTree::new());
+    tree
+}
+
+fn cbor_head(major: u8, value: u64, out: &mut Vec<u8>) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend([major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend((value as u16).to_be_bytes());
+        }
+        0x10000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend((value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend(value.to_be_bytes());
+        }
+    }
+}
+
+fn cbor_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    cbor_head(2, bytes.len() as u64, out);
+    out.extend_from_slice(bytes);
+}
+
+fn encode_hash_tree(node: &HashTreeNode, out: &mut Vec<u8>) {
+    match node {
+        HashTreeNode::Empty() => {
+            cbor_head(4, 1, out);
+            cbor_head(0, 0, out);
+        }
+        HashTreeNode::Fork(children) => {
+            cbor_head(4, 3, out);
+            cbor_head(0, 1, out);
+            encode_hash_tree(&children.0, out);
+            encode_hash_tree(&children.1, out);
+        }
+        HashTreeNode::Labeled(label, subtree) => {
+            cbor_head(4, 3, out);
+            cbor_head(0, 2, out);
+            cbor_bytes(label.as_bytes(), out);
+            encode_hash_tree(subtree, out);
+        }
+        HashTreeNode::Leaf(value) => {
+            cbor_head(4, 2, out);
+            cbor_head(0, 3, out);
+            cbor_bytes(value, out);
+        }
+        HashTreeNode::Pruned(digest) => {
+            cbor_head(4, 2, out);
+            cbor_head(0, 4, out);
+            cbor_bytes(digest, out);
+        }
+    }
+}
+
+// Self-describing CBOR, as expected by agent-side certificate verification
+fn hash_tree_cbor(tree: &HashTree) -> Vec<u8> {
+    let mut out = Vec::new();
+    cbor_head(6, 55799, &mut out);
+    encode_hash_tree(tree.as_ref(), &mut out);
+    out
+}
+
+fn publish_certified_root() {
+    let root_hash = CERTIFIED_TREE.with(|tree| tree.borrow().root_hash());
+    system::set_certified_data(&root_hash);
+}
+
+fn certify_entry(section: &[u8], land_id: LandId, value: Option<Vec<u8>>) {
+    let key = land_id.to_be_bytes();
+    CERTIFIED_TREE.with(|tree| {
+        tree.borrow_mut().modify(section, |entries| match value {
+            Some(value) => entries.insert(key.to_vec(), value),
+            None => entries.delete(&key),
+        });
+    });
+}
+
+// Must run after every update that changes the owner or listing price of `land_id`
+fn certify_land(land_id: LandId) {
+    certify_land_entries(land_id);
+    publish_certified_root();
+}
+
+// Updates the tree without publishing it; batch callers publish once when done
+fn certify_land_entries(land_id: LandId) {
+    let owner = LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id).map(|land| land.owner.as_slice().to_vec())
+    });
+    let price = MARKETPLACE.with(|marketplace| {
+        marketplace.borrow().get(&land_id).map(|listing| listing.price.to_be_bytes().to_vec())
+    });
+    certify_entry(CERTIFIED_LANDS_LABEL, land_id, owner);
+    certify_entry(CERTIFIED_LISTINGS_LABEL, land_id, price);
+}
+
+// Full rebuild, for bulk replacements of the registry
+fn rebuild_certified_data() {
+    let mut tree = empty_certified_tree();
+    tree.modify(CERTIFIED_LANDS_LABEL, |entries| {
+        LAND_STORAGE.with(|storage| {
+            for land in storage.borrow().values() {
+                entries.insert(land.id.to_be_bytes().to_vec(), land.owner.as_slice().to_vec());
+            }
+        });
+    });
+    tree.modify(CERTIFIED_LISTINGS_LABEL, |entries| {
+        MARKETPLACE.with(|marketplace| {
+            for listing in marketplace.borrow().values() {
+                entries.insert(listing.land_id.to_be_bytes().to_vec(), listing.price.to_be_bytes().to_vec());
+            }
+        });
+    });
+    CERTIFIED_TREE.with(|certified| *certified.borrow_mut() = tree);
+    publish_certified_root();
+}
+
+// Reveals the entry for `land_id` in `section`, or its neighbours so the client can
+// check absence; the other section is pruned
+fn certified_witness(section: &[u8], land_id: LandId) -> Vec<u8> {
+    let key = land_id.to_be_bytes();
+    let witness = CERTIFIED_TREE.with(|tree| {
+        tree.borrow().nested_witness(section, |entries| entries.witness(&key))
+    });
+    hash_tree_cbor(&witness)
+}
+
+#[query]
+fn get_land_owner_certified(land_id: LandId) -> CertifiedOwner {
+    CertifiedOwner {
+        owner: get_land_owner(land_id),
+        certificate: system::data_certificate(),
+        witness: certified_witness(CERTIFIED_LANDS_LABEL, land_id),
+    }
+}
+
+#[query]
+fn get_marketplace_listing_certified(land_id: LandId) -> CertifiedListing {
+    CertifiedListing {
+        land_id,
+        price: MARKETPLACE.with(|marketplace| marketplace.borrow().get(&land_id).map(|listing| listing.price)),
+        certificate: system::data_certificate(),
+        witness: certified_witness(CERTIFIED_LISTINGS_LABEL, land_id),
+    }
+}
+
+// Registry invariants
+fn build_ownership_index() -> HashMap<Principal, HashSet<LandId>> {
+    let mut index: HashMap<Principal, HashSet<LandId>> = HashMap::new();
+    LAND_STORAGE.with(|storage| {
+        for land in storage.borrow().values() {
+            index.entry(land.owner).or_default().insert(land.id);
+        }
+    });
+    index
+}
+
+fn build_coordinate_index() -> HashMap<(i32, i32, i32), LandId> {
+    LAND_STORAGE.with(|storage| {
+        storage.borrow().values()
+            .map(|land| ((land.coordinates.x, land.coordinates.y, land.coordinates.z), land.id))
+            .collect()
+    })
+}
+
+fn build_spatial_index() -> HashMap<(i32, i32), HashSet<LandId>> {
+    let mut index: HashMap<(i32, i32), HashSet<LandId>> = HashMap::new();
+    LAND_STORAGE.with(|storage| {
+        for land in storage.borrow().values() {
+            for cell in spatial_cells(&land.coordinates, &land.dimensions) {
+                index.entry(cell).or_default().insert(land.id);
+            }
+        }
+    });
+    index
+}
+
+fn build_text_index() -> HashMap<String, HashMap<LandId, u32>> {
+    let mut index: HashMap<String, HashMap<LandId, u32>> = HashMap::new();
+    LAND_STORAGE.with(|storage| {
+        for land in storage.borrow().values() {
+            for (term, weight) in land_terms(land) {
+                index.entry(term).or_default().insert(land.id, weight);
+            }
+        }
+    });
+    index
+}
+
+fn invariant_issue(store: &str, land_id: Option<LandId>, description: String) -> InvariantIssue {
+    InvariantIssue {
+        store: store.to_string(),
+        land_id,
+        description,
+    }
+}
+
+fn collect_invariant_issues() -> Vec<InvariantIssue> {
+    let mut issues = Vec::new();
+    let lands: BTreeMap<LandId, LandInfo> = LAND_STORAGE.with(|storage| {
+        storage.borrow().iter().map(|(id, land)| (*id, land.clone())).collect()
+    });
+    
+    // Primary storage
+    for (id, land) in &lands {
+        if land.id != *id {
+            issues.push(invariant_issue("LAND_STORAGE", Some(*id), format!("stored under key {} but has id {}", id, land.id)));
+        }
+    }
+    let next_id = NEXT_LAND_ID.with(|id| *id.borrow());
+    if let Some(max_id) = lands.keys().next_back() {
+        if next_id <= *max_id {
+            issues.push(invariant_issue("NEXT_LAND_ID", Some(*max_id), format!("next id {} is not above the highest id", next_id)));
+        }
+    }
+    
+    // Ownership index
+    OWNERSHIP_INDEX.with(|index| {
+        let index = index.borrow();
+        for (owner, land_ids) in index.iter() {
+            if land_ids.is_empty() {
+                issues.push(invariant_issue("OWNERSHIP_INDEX", None, format!("empty entry for {}", owner)));
+            }
+            for id in land_ids {
+                match lands.get(id) {
+                    None => issues.push(invariant_issue("OWNERSHIP_INDEX", Some(*id), "references a missing land".to_string())),
+                    Some(land) if land.owner != *owner => issues.push(invariant_issue(
+                        "OWNERSHIP_INDEX", Some(*id), format!("listed under {} but owned by {}", owner, land.owner),
+                    )),
+                    _ => {}
+                }
+            }
+        }
+        for land in lands.values() {
+            if !index.get(&land.owner).map(|ids| ids.contains(&land.id)).unwrap_or(false) {
+                issues.push(invariant_issue("OWNERSHIP_INDEX", Some(land.id), "land missing from its owner's entry".to_string()));
+            }
+        }
+    });
+    
+    // Coordinate index
+    COORDINATE_INDEX.with(|index| {
+        let index = index.borrow();
+        for (origin, id) in index.iter() {
+            match lands.get(id) {
+                None => issues.push(invariant_issue("COORDINATE_INDEX", Some(*id), format!("origin {:?} references a missing land", origin))),
+                Some(land) if (land.coordinates.x, land.coordinates.y, land.coordinates.z) != *origin => {
+                    issues.push(invariant_issue("COORDINATE_INDEX", Some(*id), format!("origin {:?} does not match the land", origin)));
+                }
+                _ => {}
+            }
+        }
+        for land in lands.values() {
+            let origin = (land.coordinates.x, land.coordinates.y, land.coordinates.z);
+            if index.get(&origin) != Some(&land.id) {
+                issues.push(invariant_issue("COORDINATE_INDEX", Some(land.id), "land origin not indexed".to_string()));
+            }
+        }
+    });
+    
+    // Marketplace
+    MARKETPLACE.with(|marketplace| {
+        for (id, listing) in marketplace.borrow().iter() {
+            if listing.land_id != *id {
+                issues.push(invariant_issue("MARKETPLACE", Some(*id), format!("stored under key {} but lists land {}", id, listing.land_id)));
+            }
+            match lands.get(id) {
+                None => issues.push(invariant_issue("MARKETPLACE", Some(*id), "listing for a missing land".to_string())),
+                Some(land) if listing.seller != land.owner => {
+                    issues.push(invariant_issue("MARKETPLACE", Some(*id), format!("seller {} no longer owns the land", listing.seller)));
+                }
+                _ => {}
+            }
+        }
+    });
+    
+    // Tombstones
+    TOMBSTONES.with(|tombstones| {
+        for id in tombstones.borrow().keys() {
+            if lands.contains_key(id) {
+                issues.push(invariant_issue("TOMBSTONES", Some(*id), "tombstoned land is also live".to_string()));
+            }
+        }
+    });
+    
+    // Derived indexes
+    if SPATIAL_INDEX.with(|index| *index.borrow() != build_spatial_index()) {
+        issues.push(invariant_issue("SPATIAL_INDEX", None, "does not match storage".to_string()));
+    }
+    if TEXT_INDEX.with(|index| *index.borrow() != build_text_index()) {
+        issues.push(invariant_issue("TEXT_INDEX", None, "does not match storage".to_string()));
+    }
+    for description in secondary_index_issues() {
+        issues.push(invariant_issue("SECONDARY_INDEXES", None, description));
+    }
+    
+    // Overlapping parcels cannot be repaired automatically, but should be reported
+    for land in lands.values() {
+        let cells = spatial_cells(&land.coordinates, &land.dimensions);
+        for other_id in spatial_candidates(cells[0], cells[cells.len() - 1]) {
+            if other_id <= land.id {
+                continue;
+            }
+            if let Some(other) = lands.get(&other_id) {
+                if coordinates_overlap(&land.coordinates, &land.dimensions, &other.coordinates, &other.dimensions) {
+                    issues.push(invariant_issue("LAND_STORAGE", Some(land.id), format!("overlaps land {}", other_id)));
+                }
+            }
+        }
+    }
+    
+    issues
+}
+
+// Rebuilds every derived index from LAND_STORAGE and drops listings that can no longer be honoured
+fn repair_registry() {
+    let lands: HashMap<LandId, LandInfo> = LAND_STORAGE.with(|storage| storage.borrow().clone());
+    
+    // Listings are rewritten in place; the secondary indexes are rebuilt below
+    let stale: Vec<ListingRecord> = MARKETPLACE.with(|marketplace| {
+        marketplace.borrow().values().cloned().collect()
+    });
+    MARKETPLACE.with(|marketplace| marketplace.borrow_mut().clear());
+    for listing in stale {
+        if let Some(land) = lands.get(&listing.land_id) {
+            if listing.seller == land.owner {
+                MARKETPLACE.with(|marketplace| {
+                    marketplace.borrow_mut().insert(land.id, ListingRecord {
+                        land_id: land.id,
+                        ..listing
+                    });
+                });
+            }
+        }
+    }
+    
+    let ownership = build_ownership_index();
+    OWNERSHIP_INDEX.with(|index| *index.borrow_mut() = ownership);
+    let coordinates = build_coordinate_index();
+    COORDINATE_INDEX.with(|index| *index.borrow_mut() = coordinates);
+    let spatial = build_spatial_index();
+    SPATIAL_INDEX.with(|index| *index.borrow_mut() = spatial);
+    let text = build_text_index();
+    TEXT_INDEX.with(|index| *index.borrow_mut() = text);
+    rebuild_secondary_indexes();
+    
+    // Tombstoned ids stay reserved so a restore can never collide
+    let max_tombstone_id = TOMBSTONES.with(|tombstones| tombstones.borrow().keys().max().copied());
+    if let Some(max_id) = lands.keys().max().copied().max(max_tombstone_id) {
+        NEXT_LAND_ID.with(|next_id| {
+            let mut next_id = next_id.borrow_mut();
+            if *next_id <= max_id {
+                *next_id = max_id + 1;
+            }
+        });
+    }
+    
+    rebuild_certified_data();
+}
+
+#[query]
+fn check_invariants() -> LandRegistryResult<Vec<InvariantIssue>> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::Moderator, Role::SupportAgent]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    Ok(collect_invariant_issues())
+}
+
+#[update]
+fn repair() -> LandRegistryResult<RepairReport> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::Moderator]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let found = collect_invariant_issues();
+    repair_registry();
+    let remaining = collect_invariant_issues();
+    
+    record_audit(
+        caller, AuditAction::RegistryRepaired, AuditTarget::Registry,
+        Some(found.len().to_string()), Some(remaining.len().to_string()),
+    );
+    
+    Ok(RepairReport { found, remaining })
+}
+
+// Helper query functions
+#[query]
+fn get_total_supply() -> u64 {
+    LAND_STORAGE.with(|storage| storage.borrow().len() as u64)
+}
+
+#[query]
+fn get_next_land_id() -> LandId {
+    NEXT_LAND_ID.with(|id| *id.borrow())
+}
+
+#[query]
+fn verify_land_ownership(land_id: LandId, claimed_owner: Principal) -> bool {
+    LAND_STORAGE.with(|storage| {
+        storage.borrow().get(&land_id)
+            .map(|land| land.owner == claimed_owner)
+            .unwrap_or(false)
+    })
+}
+
+// Runs the register_land checks over a set of lands to be restored, against the given
+// districts and attribute schemas, and reports every problem rather than the first
+fn validate_snapshot_lands(
+    lands: &[LandInfo],
+    districts: &[District],
+    schemas: &HashMap<String, AttributeSchema>,
+    parameters: &RegistryParameters,
+) -> SnapshotValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut seen_ids = HashSet::new();
+    // cell -> indices into `lands` of valid parcels already checked
+    let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    
+    for (i, land) in lands.iter().enumerate() {
+        if !seen_ids.insert(land.id) {
+            errors.push(invariant_issue("lands", Some(land.id), "duplicate land id".to_string()));
+        }
+        if land.owner == Principal::anonymous() {
+            errors.push(invariant_issue("lands", Some(land.id), "owned by the anonymous principal".to_string()));
+        }
+        if land.description.trim().is_empty() {
+            warnings.push(invariant_issue("lands", Some(land.id), "empty description".to_string()));
+        }
+        if attributes_conform(&land.metadata, schemas).is_err() {
+            warnings.push(invariant_issue("lands", Some(land.id), "attributes do not match the schemas".to_string()));
+        }
+        
+        let coordinates_valid = within_bounds(&land.coordinates, &parameters.world_min, &parameters.world_max);
+        let dimensions_valid = validate_dimensions(&land.dimensions);
+        if !coordinates_valid {
+            errors.push(invariant_issue("lands", Some(land.id), format!("invalid coordinates {:?}", land.coordinates)));
+        }
+        if !dimensions_valid {
+            errors.push(invariant_issue("lands", Some(land.id), format!("invalid dimensions {:?}", land.dimensions)));
+        }
+        // Geometry checks only make sense for parcels inside the world bounds
+        if !coordinates_valid || !dimensions_valid {
+            continue;
+        }
+        
+        let violates_zoning = districts.iter()
+            .filter(|district| district_intersects(district, &land.coordinates, &land.dimensions))
+            .any(|district| !zoning_permits(&district.zoning, &land.dimensions, &land.land_type));
+        if violates_zoning {
+            warnings.push(invariant_issue("lands", Some(land.id), "violates district zoning".to_string()));
+        }
+        
+        let mut overlapping = BTreeSet::new();
+        for cell in spatial_cells(&land.coordinates, &land.dimensions) {
+            let checked = grid.entry(cell).or_default();
+            for &j in checked.iter() {
+                let other = &lands[j];
+                if coordinates_overlap(&land.coordinates, &land.dimensions, &other.coordinates, &other.dimensions) {
+                    overlapping.insert(other.id);
+                }
+            }
+            checked.push(i);
+        }
+        for other_id in overlapping {
+            errors.push(invariant_issue("lands", Some(land.id), format!("overlaps land {}", other_id)));
+        }
+    }
+    
+    SnapshotValidation { errors, warnings }
+}
+
+// Checks `lands` against the current districts, schemas and world bounds
+fn validate_against_registry(lands: &[LandInfo]) -> SnapshotValidation {
+    let districts: Vec<District> = DISTRICTS.with(|districts| districts.borrow().values().cloned().collect());
+    let parameters = REGISTRY_PARAMETERS.with(|parameters| parameters.borrow().clone());
+    ATTRIBUTE_SCHEMAS.with(|schemas| validate_snapshot_lands(lands, &districts, &schemas.borrow(), &parameters))
+}
+
+#[query]
+fn validate_snapshot(lands: Vec<LandInfo>) -> LandRegistryResult<SnapshotValidation> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    Ok(validate_against_registry(&lands))
+}
+
+// Backup and restore functions for data migration
+#[query]
+fn backup_lands() -> Vec<LandInfo> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if authorized {
+        LAND_STORAGE.with(|storage| {
+            storage.borrow().values().cloned().collect()
+        })
+    } else {
+        Vec::new()
+    }
+}
+
+#[update]
+fn restore_lands(lands: Vec<LandInfo>) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if required_approvals() > 1 {
+        return Err(LandRegistryError::ApprovalRequired);
+    }
+    
+    replace_lands(lands, caller)
+}
+
+fn replace_lands(lands: Vec<LandInfo>, actor: Principal) -> LandRegistryResult<()> {
+    // Refuse the whole snapshot before touching storage if any land is structurally invalid
+    if !validate_against_registry(&lands).errors.is_empty() {
+        return Err(LandRegistryError::InvalidSnapshot);
+    }
+    
+    let previous_owners: HashMap<LandId, Principal> = LAND_STORAGE.with(|storage| {
+        storage.borrow().values().map(|land| (land.id, land.owner)).collect()
+    });
+    
+    // Clear existing data
+    LAND_STORAGE.with(|storage| storage.borrow_mut().clear());
+    OWNERSHIP_INDEX.with(|index| index.borrow_mut().clear());
+    COORDINATE_INDEX.with(|index| index.borrow_mut().clear());
+    SPATIAL_INDEX.with(|index| index.borrow_mut().clear());
+    TEXT_INDEX.with(|index| index.borrow_mut().clear());
+    
+    // Grants were made by the owners being replaced
+    OPERATORS.with(|operators| operators.borrow_mut().clear());
+    
+    // Restored ids replace any tombstones they collide with
+    TOMBSTONES.with(|tombstones| {
+        let mut tombstones = tombstones.borrow_mut();
+        for land in &lands {
+            tombstones.remove(&land.id);
+        }
+    });
+    
+    // Lands are too bulky to diff; the entry records how many were restored
+    record_audit(actor, AuditAction::LandsRestored, AuditTarget::Registry, None, Some(lands.len().to_string()));
+    
+    // Restore lands
+    for land in lands {
+        LAND_STORAGE.with(|storage| {
+            storage.borrow_mut().insert(land.id, land.clone());
+        });
+        
+        index_land_spatially(&land);
+        index_land_text(&land);
         
         OWNERSHIP_INDEX.with(|index| {
             index.borrow_mut()
@@ -845,34 +6315,517 @@ fn restore_lands(lands: Vec<LandInfo>) -> LandRegistryResult<()> {
                 .insert(land.id);
         });
         
-        COORDINATE_INDEX.with(|index| {
-            index.borrow_mut().insert(
-                (land.coordinates.x, land.coordinates.y, land.coordinates.z),
-                land.id,
-            );
-        });
+        COORDINATE_INDEX.with(|index| {
+            index.borrow_mut().insert(
+                (land.coordinates.x, land.coordinates.y, land.coordinates.z),
+                land.id,
+            );
+        });
+        
+        // Update next_land_id if necessary
+        NEXT_LAND_ID.with(|next_id| {
+            let mut next_id = next_id.borrow_mut();
+            if land.id >= *next_id {
+                *next_id = land.id + 1;
+            }
+        });
+    }
+    
+    reconcile_with_restored_lands(actor, &previous_owners);
+    
+    rebuild_secondary_indexes();
+    
+    rebuild_certified_data();
+    
+    Ok(())
+}
+
+// Listings, requests and disputes that refer to parcels the restore dropped or handed to
+// someone else cannot go ahead, so they are withdrawn the way a removal would withdraw them
+fn reconcile_with_restored_lands(actor: Principal, previous_owners: &HashMap<LandId, Principal>) {
+    const NOTE: &str = "Parcel not in restored snapshot";
+    let owner_of = |land_id: LandId| LAND_STORAGE.with(|storage| storage.borrow().get(&land_id).map(|land| land.owner));
+    
+    // Offers were made to the owner being replaced
+    OFFERS.with(|offers| {
+        offers.borrow_mut().retain(|_, offer| {
+            owner_of(offer.land_id).is_some() && owner_of(offer.land_id) == previous_owners.get(&offer.land_id).copied()
+        })
+    });
+    
+    let stale_listings: Vec<LandId> = MARKETPLACE.with(|marketplace| {
+        marketplace.borrow().values()
+            .filter(|listing| owner_of(listing.land_id) != Some(listing.seller))
+            .map(|listing| listing.land_id)
+            .collect()
+    });
+    for land_id in stale_listings {
+        if let Some(listing) = remove_listing(land_id) {
+            record_audit(actor, AuditAction::LandDelisted, AuditTarget::Land(land_id), audit_json(&listing), Some(NOTE.to_string()));
+        }
+    }
+    
+    let orphaned_rezoning: BTreeSet<LandId> = REZONING_REQUESTS.with(|requests| {
+        requests.borrow().values()
+            .filter(|request| request.status == RezoningStatus::Pending && owner_of(request.land_id).is_none())
+            .map(|request| request.land_id)
+            .collect()
+    });
+    for land_id in orphaned_rezoning {
+        cancel_pending_rezoning(land_id, actor, NOTE);
+    }
+    
+    let now = time();
+    let orphaned_disputes: Vec<DisputeId> = DISPUTES.with(|disputes| {
+        disputes.borrow().values()
+            .filter(|dispute| dispute.status == DisputeStatus::Open && owner_of(dispute.land_id).is_none())
+            .map(|dispute| dispute.id)
+            .collect()
+    });
+    for dispute_id in orphaned_disputes {
+        let (before, after) = DISPUTES.with(|disputes| {
+            let mut disputes = disputes.borrow_mut();
+            let stored = disputes.get_mut(&dispute_id).expect("dispute listed above");
+            let before = stored.clone();
+            stored.status = DisputeStatus::Resolved;
+            stored.outcome = Some(DisputeOutcome::Dismissed);
+            stored.resolution_note = Some(NOTE.to_string());
+            stored.resolved_by = Some(actor);
+            stored.resolved_at = Some(now);
+            (before, stored.clone())
+        });
+        record_audit(actor, AuditAction::DisputeResolved, AuditTarget::Dispute(dispute_id), audit_json(&before), audit_json(&after));
+    }
+    
+    // A recovery moves whatever its owner holds when it runs, so it only goes stale once
+    // the owner holds nothing
+    let orphaned_recoveries: Vec<RecoveryRequestId> = RECOVERY_REQUESTS.with(|requests| {
+        requests.borrow().values()
+            .filter(|request| matches!(request.status, RecoveryStatus::Pending | RecoveryStatus::Challenge))
+            .filter(|request| OWNERSHIP_INDEX.with(|index| {
+                index.borrow().get(&request.owner).map(|ids| ids.is_empty()).unwrap_or(true)
+            }))
+            .map(|request| request.id)
+            .collect()
+    });
+    for request_id in orphaned_recoveries {
+        RECOVERY_REQUESTS.with(|requests| {
+            if let Some(stored) = requests.borrow_mut().get_mut(&request_id) {
+                stored.status = RecoveryStatus::Cancelled;
+                stored.resolved_at = Some(now);
+            }
+        });
+        record_audit(actor, AuditAction::RecoveryCancelled, AuditTarget::RecoveryRequest(request_id), None, Some(NOTE.to_string()));
+    }
+}
+
+// Snapshot functions for migrating the full registry between canisters
+fn snapshot_checksum(bytes: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher.finalize().to_vec()
+}
+
+fn capture_snapshot() -> RegistrySnapshot {
+    let mut lands: Vec<LandInfo> = LAND_STORAGE.with(|storage| storage.borrow().values().cloned().collect());
+    lands.sort_by_key(|land| land.id);
+    let mut listings: Vec<ListingRecord> = MARKETPLACE.with(|marketplace| marketplace.borrow().values().cloned().collect());
+    listings.sort_by_key(|listing| listing.land_id);
+    let mut roles: Vec<RoleAssignment> = ROLES.with(|assignments| {
+        assignments.borrow().iter()
+            .map(|(principal, held)| RoleAssignment {
+                principal: *principal,
+                roles: held.iter().copied().collect(),
+            })
+            .collect()
+    });
+    roles.sort_by_key(|assignment| assignment.principal);
+    let mut districts: Vec<District> = DISTRICTS.with(|districts| districts.borrow().values().cloned().collect());
+    districts.sort_by_key(|district| district.id);
+    let mut rezoning_requests: Vec<RezoningRequest> = REZONING_REQUESTS.with(|requests| requests.borrow().values().cloned().collect());
+    rezoning_requests.sort_by_key(|request| request.id);
+    let mut attribute_schemas: Vec<AttributeSchema> = ATTRIBUTE_SCHEMAS.with(|schemas| schemas.borrow().values().cloned().collect());
+    attribute_schemas.sort_by(|a, b| a.key.cmp(&b.key));
+    let mut admin_proposals: Vec<AdminProposal> = ADMIN_PROPOSALS.with(|proposals| proposals.borrow().values().cloned().collect());
+    admin_proposals.sort_by_key(|proposal| proposal.id);
+    let mut governance_proposals: Vec<GovernanceProposal> = GOVERNANCE_PROPOSALS.with(|proposals| proposals.borrow().values().cloned().collect());
+    governance_proposals.sort_by_key(|proposal| proposal.id);
+    let mut tombstones: Vec<Tombstone> = TOMBSTONES.with(|tombstones| tombstones.borrow().values().cloned().collect());
+    tombstones.sort_by_key(|tombstone| tombstone.land.id);
+    let mut disputes: Vec<Dispute> = DISPUTES.with(|disputes| disputes.borrow().values().cloned().collect());
+    disputes.sort_by_key(|dispute| dispute.id);
+    let mut operators: Vec<OperatorGrant> = OPERATORS.with(|operators| {
+        operators.borrow().values().flat_map(|grants| grants.values().cloned()).collect()
+    });
+    operators.sort_by_key(|grant| (grant.land_id, grant.operator));
+    let mut guardians: Vec<GuardianConfig> = GUARDIANS.with(|guardians| guardians.borrow().values().cloned().collect());
+    guardians.sort_by_key(|config| config.owner);
+    let mut last_activity: Vec<(Principal, Timestamp)> = LAST_ACTIVITY.with(|activity| {
+        activity.borrow().iter().map(|(principal, at)| (*principal, *at)).collect()
+    });
+    last_activity.sort();
+    let mut recovery_requests: Vec<RecoveryRequest> = RECOVERY_REQUESTS.with(|requests| requests.borrow().values().cloned().collect());
+    recovery_requests.sort_by_key(|request| request.id);
+    
+    RegistrySnapshot {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        lands,
+        listings,
+        transactions: TRANSACTION_HISTORY.with(|history| history.borrow().clone()),
+        roles,
+        role_changes: ROLE_CHANGES.with(|changes| changes.borrow().clone()),
+        next_land_id: NEXT_LAND_ID.with(|next_id| *next_id.borrow()),
+        districts,
+        next_district_id: NEXT_DISTRICT_ID.with(|next_id| *next_id.borrow()),
+        rezoning_requests,
+        next_rezoning_request_id: NEXT_REZONING_REQUEST_ID.with(|next_id| *next_id.borrow()),
+        attribute_schemas,
+        approval_threshold: APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow()),
+        admin_proposals,
+        next_admin_proposal_id: NEXT_ADMIN_PROPOSAL_ID.with(|next_id| *next_id.borrow()),
+        registry_parameters: REGISTRY_PARAMETERS.with(|parameters| parameters.borrow().clone()),
+        governance_config: GOVERNANCE_CONFIG.with(|config| config.borrow().clone()),
+        governance_proposals,
+        next_governance_proposal_id: NEXT_GOVERNANCE_PROPOSAL_ID.with(|next_id| *next_id.borrow()),
+        pauses: get_pause_status(),
+        audit_log: AUDIT_LOG.with(|log| log.borrow().clone()),
+        tombstones,
+        next_hold_id: NEXT_HOLD_ID.with(|next_id| *next_id.borrow()),
+        disputes,
+        next_dispute_id: NEXT_DISPUTE_ID.with(|next_id| *next_id.borrow()),
+        operators,
+        guardians,
+        last_activity,
+        recovery_requests,
+        next_recovery_request_id: NEXT_RECOVERY_REQUEST_ID.with(|next_id| *next_id.borrow()),
+        offers: OFFERS.with(|offers| offers.borrow().values().cloned().collect()),
+        next_offer_id: NEXT_OFFER_ID.with(|next_id| *next_id.borrow()),
+    }
+}
+
+// Replaces every primary store except the audit log with the snapshot, then rebuilds
+// the derived indexes
+fn apply_snapshot(snapshot: RegistrySnapshot) {
+    LAND_STORAGE.with(|storage| {
+        *storage.borrow_mut() = snapshot.lands.into_iter().map(|land| (land.id, land)).collect();
+    });
+    MARKETPLACE.with(|marketplace| {
+        *marketplace.borrow_mut() = snapshot.listings.into_iter().map(|listing| (listing.land_id, listing)).collect();
+    });
+    TRANSACTION_HISTORY.with(|history| *history.borrow_mut() = snapshot.transactions);
+    ROLES.with(|assignments| {
+        *assignments.borrow_mut() = snapshot.roles.into_iter()
+            .filter(|assignment| !assignment.roles.is_empty())
+            .map(|assignment| (assignment.principal, assignment.roles.into_iter().collect()))
+            .collect();
+    });
+    ROLE_CHANGES.with(|changes| *changes.borrow_mut() = snapshot.role_changes);
+    NEXT_LAND_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_land_id);
+    DISTRICTS.with(|districts| {
+        *districts.borrow_mut() = snapshot.districts.into_iter().map(|district| (district.id, district)).collect();
+    });
+    NEXT_DISTRICT_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_district_id);
+    REZONING_REQUESTS.with(|requests| {
+        *requests.borrow_mut() = snapshot.rezoning_requests.into_iter().map(|request| (request.id, request)).collect();
+    });
+    NEXT_REZONING_REQUEST_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_rezoning_request_id);
+    ATTRIBUTE_SCHEMAS.with(|schemas| {
+        *schemas.borrow_mut() = snapshot.attribute_schemas.into_iter().map(|schema| (schema.key.clone(), schema)).collect();
+    });
+    APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow_mut() = snapshot.approval_threshold.max(1));
+    ADMIN_PROPOSALS.with(|proposals| {
+        *proposals.borrow_mut() = snapshot.admin_proposals.into_iter().map(|proposal| (proposal.id, proposal)).collect();
+    });
+    NEXT_ADMIN_PROPOSAL_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_admin_proposal_id);
+    REGISTRY_PARAMETERS.with(|parameters| *parameters.borrow_mut() = snapshot.registry_parameters);
+    GOVERNANCE_CONFIG.with(|config| *config.borrow_mut() = snapshot.governance_config);
+    GOVERNANCE_PROPOSALS.with(|proposals| {
+        *proposals.borrow_mut() = snapshot.governance_proposals.into_iter().map(|proposal| (proposal.id, proposal)).collect();
+    });
+    NEXT_GOVERNANCE_PROPOSAL_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_governance_proposal_id);
+    PAUSES.with(|pauses| {
+        *pauses.borrow_mut() = snapshot.pauses.into_iter().map(|pause| (pause.scope, pause)).collect();
+    });
+    // The log is never rewritten: imported entries are appended after the local ones,
+    // which keep their ids
+    AUDIT_LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        let offset = log.len();
+        log.extend(snapshot.audit_log.into_iter()
+            .enumerate()
+            .map(|(index, entry)| AuditEntry { id: (offset + index) as AuditEntryId, ..entry }));
+    });
+    TOMBSTONES.with(|tombstones| {
+        *tombstones.borrow_mut() = snapshot.tombstones.into_iter().map(|tombstone| (tombstone.land.id, tombstone)).collect();
+    });
+    NEXT_HOLD_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_hold_id);
+    DISPUTES.with(|disputes| {
+        *disputes.borrow_mut() = snapshot.disputes.into_iter().map(|dispute| (dispute.id, dispute)).collect();
+    });
+    NEXT_DISPUTE_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_dispute_id);
+    OPERATORS.with(|operators| {
+        let mut operators = operators.borrow_mut();
+        operators.clear();
+        for grant in snapshot.operators {
+            operators.entry(grant.land_id).or_default().insert(grant.operator, grant);
+        }
+    });
+    GUARDIANS.with(|guardians| {
+        *guardians.borrow_mut() = snapshot.guardians.into_iter().map(|config| (config.owner, config)).collect();
+    });
+    LAST_ACTIVITY.with(|activity| *activity.borrow_mut() = snapshot.last_activity.into_iter().collect());
+    RECOVERY_REQUESTS.with(|requests| {
+        *requests.borrow_mut() = snapshot.recovery_requests.into_iter().map(|request| (request.id, request)).collect();
+    });
+    NEXT_RECOVERY_REQUEST_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_recovery_request_id);
+    OFFERS.with(|offers| {
+        *offers.borrow_mut() = snapshot.offers.into_iter().map(|offer| (offer.id, offer)).collect();
+    });
+    NEXT_OFFER_ID.with(|next_id| *next_id.borrow_mut() = snapshot.next_offer_id);
+    
+    repair_registry();
+    schedule_next_timer();
+}
+
+#[update]
+fn begin_snapshot_export() -> LandRegistryResult<SnapshotManifest> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let bytes = candid::encode_one(capture_snapshot()).map_err(|_| LandRegistryError::InvalidSnapshot)?;
+    let snapshot_id = NEXT_SNAPSHOT_ID.with(|next_id| {
+        let mut next_id = next_id.borrow_mut();
+        let id = *next_id;
+        *next_id += 1;
+        id
+    });
+    
+    let manifest = SnapshotManifest {
+        snapshot_id,
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        total_bytes: bytes.len() as u64,
+        chunk_count: bytes.len().div_ceil(SNAPSHOT_CHUNK_SIZE as usize) as u32,
+        chunk_size: SNAPSHOT_CHUNK_SIZE,
+        checksum: snapshot_checksum(&bytes),
+        created_at: time(),
+    };
+    
+    // Only one export is buffered at a time
+    SNAPSHOT_EXPORT.with(|export| {
+        *export.borrow_mut() = Some((manifest.clone(), bytes));
+    });
+    
+    Ok(manifest)
+}
+
+#[query]
+fn export_snapshot_chunk(snapshot_id: u64, index: u32) -> LandRegistryResult<Vec<u8>> {
+    let caller = system::caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    SNAPSHOT_EXPORT.with(|export| {
+        match export.borrow().as_ref() {
+            Some((manifest, bytes)) if manifest.snapshot_id == snapshot_id => {
+                if index >= manifest.chunk_count {
+                    return Err(LandRegistryError::InvalidInput);
+                }
+                let start = index as usize * manifest.chunk_size as usize;
+                let end = (start + manifest.chunk_size as usize).min(bytes.len());
+                Ok(bytes[start..end].to_vec())
+            }
+            _ => Err(LandRegistryError::InvalidSnapshot),
+        }
+    })
+}
+
+#[update]
+fn finish_snapshot_export(snapshot_id: u64) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    SNAPSHOT_EXPORT.with(|export| {
+        let mut export = export.borrow_mut();
+        match export.as_ref() {
+            Some((manifest, _)) if manifest.snapshot_id == snapshot_id => {
+                *export = None;
+                Ok(())
+            }
+            _ => Err(LandRegistryError::InvalidSnapshot),
+        }
+    })
+}
+
+#[update]
+fn begin_snapshot_import(manifest: SnapshotManifest) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if manifest.format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(LandRegistryError::InvalidSnapshot);
+    }
+    if manifest.chunk_size == 0 || manifest.chunk_size > SNAPSHOT_CHUNK_SIZE {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    let expected_chunks = manifest.total_bytes.div_ceil(manifest.chunk_size as u64);
+    if manifest.chunk_count as u64 != expected_chunks || manifest.checksum.len() != 32 {
+        return Err(LandRegistryError::InvalidInput);
+    }
+    
+    // Starting a new import discards any partial upload
+    SNAPSHOT_IMPORT.with(|import| {
+        *import.borrow_mut() = Some(SnapshotImport {
+            manifest,
+            chunks: BTreeMap::new(),
+        });
+    });
+    
+    Ok(())
+}
+
+#[update]
+fn import_snapshot_chunk(index: u32, bytes: Vec<u8>) -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    SNAPSHOT_IMPORT.with(|import| {
+        let mut import = import.borrow_mut();
+        let import = import.as_mut().ok_or(LandRegistryError::InvalidSnapshot)?;
+        let manifest = &import.manifest;
+        
+        if index >= manifest.chunk_count {
+            return Err(LandRegistryError::InvalidInput);
+        }
+        // Every chunk but the last is exactly chunk_size bytes
+        let start = index as u64 * manifest.chunk_size as u64;
+        let expected_len = (manifest.total_bytes - start).min(manifest.chunk_size as u64);
+        if bytes.len() as u64 != expected_len {
+            return Err(LandRegistryError::InvalidInput);
+        }
         
-        // Update next_land_id if necessary
-        NEXT_LAND_ID.with(|next_id| {
-            let mut next_id = next_id.borrow_mut();
-            if land.id >= *next_id {
-                *next_id = land.id + 1;
-            }
-        });
+        import.chunks.insert(index, bytes);
+        Ok(())
+    })
+}
+
+#[update]
+fn commit_snapshot_import() -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    if required_approvals() > 1 {
+        return Err(LandRegistryError::ApprovalRequired);
+    }
+    
+    commit_pending_import(caller, None)
+}
+
+// `importer` must remain a SuperAdmin in the snapshot; `expected_checksum` pins the
+// upload an approved proposal was made against
+fn commit_pending_import(importer: Principal, expected_checksum: Option<&[u8]>) -> LandRegistryResult<()> {
+    let pinned = SNAPSHOT_IMPORT.with(|import| {
+        import.borrow().as_ref()
+            .map(|import| expected_checksum.map(|checksum| import.manifest.checksum == checksum).unwrap_or(true))
+            .unwrap_or(false)
+    });
+    if !pinned {
+        return Err(LandRegistryError::InvalidSnapshot);
     }
     
+    let import = SNAPSHOT_IMPORT.with(|import| import.borrow_mut().take())
+        .ok_or(LandRegistryError::InvalidSnapshot)?;
+    let manifest = &import.manifest;
+    
+    // Decode and verify everything before touching any store
+    if import.chunks.len() as u32 != manifest.chunk_count {
+        // Keep the partial upload so the missing chunks can still be sent
+        SNAPSHOT_IMPORT.with(|pending| *pending.borrow_mut() = Some(import));
+        return Err(LandRegistryError::InvalidSnapshot);
+    }
+    let bytes: Vec<u8> = import.chunks.into_values().flatten().collect();
+    if bytes.len() as u64 != manifest.total_bytes || snapshot_checksum(&bytes) != manifest.checksum {
+        return Err(LandRegistryError::InvalidSnapshot);
+    }
+    let snapshot: RegistrySnapshot = candid::decode_one(&bytes).map_err(|_| LandRegistryError::InvalidSnapshot)?;
+    if snapshot.format_version != manifest.format_version {
+        return Err(LandRegistryError::InvalidSnapshot);
+    }
+    
+    // Refuse snapshots that would lock the importing admin out
+    let keeps_super_admin = snapshot.roles.iter()
+        .any(|assignment| assignment.principal == importer && assignment.roles.contains(&Role::SuperAdmin));
+    if !keeps_super_admin {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    let schemas: HashMap<String, AttributeSchema> = snapshot.attribute_schemas.iter()
+        .map(|schema| (schema.key.clone(), schema.clone()))
+        .collect();
+    let validation = validate_snapshot_lands(
+        &snapshot.lands, &snapshot.districts, &schemas, &snapshot.registry_parameters,
+    );
+    if !validation.errors.is_empty() {
+        return Err(LandRegistryError::InvalidSnapshot);
+    }
+    
+    let checksum = audit_json(&manifest.checksum);
+    apply_snapshot(snapshot);
+    record_audit(importer, AuditAction::SnapshotImported, AuditTarget::Registry, None, checksum);
+    
     Ok(())
 }
 
+#[update]
+fn cancel_snapshot_import() -> LandRegistryResult<()> {
+    let caller = active_caller();
+    let authorized = has_role(&caller, &[Role::SuperAdmin]);
+    
+    if !authorized {
+        return Err(LandRegistryError::Unauthorized);
+    }
+    
+    SNAPSHOT_IMPORT.with(|import| import.borrow_mut().take())
+        .map(|_| ())
+        .ok_or(LandRegistryError::InvalidSnapshot)
+}
+
 // Additional utility functions for better land management
 #[query]
-fn get_lands_by_type(land_type: LandType) -> Vec<LandInfo> {
-    LAND_STORAGE.with(|storage| {
-        storage.borrow().values()
-            .filter(|land| std::mem::discriminant(&land.land_type) == std::mem::discriminant(&land_type))
-            .cloned()
+fn get_lands_by_type(land_type: LandType, page: PageRequest) -> Page<LandInfo> {
+    let land_ids = SECONDARY_INDEXES.with(|indexes| {
+        indexes.borrow().by_type.get(&land_type).cloned().unwrap_or_default()
+    });
+    let lands: Vec<LandInfo> = LAND_STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        land_ids.iter()
+            .filter_map(|id| storage.get(id).cloned())
             .collect()
-    })
+    });
+    
+    land_page(lands, &page)
 }
 
 #[query]
@@ -906,5 +6859,840 @@ fn calculate_total_land_area() -> u64 {
     })
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn principal(byte: u8) -> Principal {
+        Principal::from_slice(&[byte; 29])
+    }
+    
+    fn land(id: LandId, owner: Principal, x: i32, land_type: LandType) -> LandInfo {
+        LandInfo {
+            id,
+            owner,
+            coordinates: Coordinates { x, y: 0, z: 0 },
+            dimensions: Dimensions { width: 10, height: 10, depth: 1 },
+            land_type,
+            description: format!("Parcel {} by the river", id),
+            metadata: None,
+            created_at: 0,
+            last_updated: 0,
+            holds: None,
+        }
+    }
+    
+    fn add_land(land: LandInfo) {
+        NEXT_LAND_ID.with(|next_id| {
+            let next = (*next_id.borrow()).max(land.id + 1);
+            *next_id.borrow_mut() = next;
+        });
+        insert_land(land);
+    }
+    
+    fn list(land_id: LandId, seller: Principal, price: Price) {
+        insert_listing(ListingRecord { land_id, seller, listed_by: seller, price, listed_at: price * 10 });
+    }
+    
+    fn assert_consistent() {
+        let issues = collect_invariant_issues();
+        assert!(issues.is_empty(), "unexpected invariant issues: {:?}", issues);
+    }
+    
+    fn owned_by(owner: Principal) -> HashSet<LandId> {
+        OWNERSHIP_INDEX.with(|index| index.borrow().get(&owner).cloned().unwrap_or_default())
+    }
+    
+    fn as_caller(caller: Principal) {
+        system::set_caller(caller);
+    }
+    
+    // Registers a 10x10 residential plot at (x, 0, 0) through the public endpoint
+    fn register(owner: Principal, x: i32) -> LandId {
+        as_caller(owner);
+        register_land(LandRegistration {
+            coordinates: Coordinates { x, y: 0, z: 0 },
+            dimensions: Dimensions { width: 10, height: 10, depth: 1 },
+            land_type: LandType::Residential,
+            description: format!("Plot at {}", x),
+            metadata: None,
+        }).unwrap()
+    }
+    
+    fn current_version(land_id: LandId) -> u64 {
+        get_marketplace_listing(land_id).unwrap().version
+    }
+    
+    fn grant(principal: Principal, role: Role) {
+        assign_role(principal, role, principal);
+    }
+    
+    // A district covering x in [min_x, max_x) that only allows `allowed`
+    fn add_district(id: DistrictId, min_x: i32, max_x: i32, allowed: LandType) {
+        let district = District {
+            id,
+            name: format!("District {}", id),
+            min_coords: Coordinates { x: min_x, y: -1000, z: -100 },
+            max_coords: Coordinates { x: max_x, y: 1000, z: 100 },
+            zoning: ZoningRules { allowed_land_types: vec![allowed], max_parcel_area: None, max_build_height: None },
+            created_at: 0,
+            last_updated: 0,
+        };
+        DISTRICTS.with(|districts| districts.borrow_mut().insert(id, district));
+    }
+    
+    fn page(limit: u32, cursor: Option<PageCursor>, direction: Option<SortDirection>) -> PageRequest {
+        PageRequest { cursor, limit: Some(limit), sort_by: None, direction }
+    }
+    
+    // Follows next_cursor until the last page and returns everything seen
+    fn collect_pages<T>(limit: u32, direction: Option<SortDirection>, fetch: impl Fn(PageRequest) -> Page<T>) -> Vec<T> {
+        let mut items = Vec::new();
+        let mut cursor = None;
+        loop {
+            let next = fetch(page(limit, cursor, direction.clone()));
+            items.extend(next.items);
+            match next.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => return items,
+            }
+        }
+    }
+    
+    #[test]
+    fn insert_indexes_every_store() {
+        let alice = principal(1);
+        add_land(land(0, alice, 0, LandType::Residential));
+        add_land(land(1, alice, 100, LandType::Commercial));
+        
+        assert_consistent();
+        assert_eq!(owned_by(alice), HashSet::from([0, 1]));
+        SECONDARY_INDEXES.with(|indexes| {
+            let indexes = indexes.borrow();
+            assert_eq!(indexes.by_type.get(&LandType::Residential), Some(&BTreeSet::from([0])));
+            assert_eq!(indexes.by_type.get(&LandType::Commercial), Some(&BTreeSet::from([1])));
+        });
+        assert!(text_scores("river").contains_key(&1));
+    }
+    
+    #[test]
+    fn remove_clears_every_index() {
+        let alice = principal(1);
+        add_land(land(0, alice, 0, LandType::Residential));
+        add_land(land(1, alice, 100, LandType::Residential));
+        list(0, alice, 500);
+        
+        let removed = remove_land_record(0).expect("land 0 exists");
+        
+        assert_eq!(removed.id, 0);
+        assert_consistent();
+        assert_eq!(owned_by(alice), HashSet::from([1]));
+        assert!(MARKETPLACE.with(|marketplace| marketplace.borrow().is_empty()));
+        assert!(!check_land_overlap(&removed.coordinates, &removed.dimensions));
+        SECONDARY_INDEXES.with(|indexes| {
+            let indexes = indexes.borrow();
+            assert_eq!(indexes.by_type.get(&LandType::Residential), Some(&BTreeSet::from([1])));
+            assert!(indexes.by_price.is_empty());
+            assert!(indexes.for_sale_by_type.is_empty());
+        });
+    }
+    
+    #[test]
+    fn transfer_moves_ownership_and_withdraws_listing() {
+        let (alice, bob) = (principal(1), principal(2));
+        add_land(land(0, alice, 0, LandType::Industrial));
+        list(0, alice, 500);
+        
+        let (previous, updated) = set_land_owner(0, bob, 42).expect("land 0 exists");
+        
+        assert_eq!(previous.owner, alice);
+        assert_eq!(updated.owner, bob);
+        assert_eq!(updated.last_updated, 42);
+        assert_consistent();
+        assert!(owned_by(alice).is_empty());
+        assert_eq!(owned_by(bob), HashSet::from([0]));
+        assert!(MARKETPLACE.with(|marketplace| marketplace.borrow().is_empty()));
+    }
+    
+    #[test]
+    fn listing_indexes_follow_the_marketplace() {
+        let alice = principal(1);
+        add_land(land(0, alice, 0, LandType::Mixed));
+        add_land(land(1, alice, 100, LandType::Mixed));
+        list(0, alice, 500);
+        list(1, alice, 300);
+        // Relisting replaces the old price rather than adding a second entry
+        list(0, alice, 700);
+        
+        assert_consistent();
+        SECONDARY_INDEXES.with(|indexes| {
+            let indexes = indexes.borrow();
+            assert_eq!(indexes.by_price, BTreeSet::from([(300, 1), (700, 0)]));
+            assert_eq!(indexes.by_listed_at, BTreeSet::from([(3000, 1), (7000, 0)]));
+            assert_eq!(indexes.for_sale_by_type.get(&LandType::Mixed), Some(&BTreeSet::from([0, 1])));
+        });
+        
+        remove_listing(1);
+        
+        assert_consistent();
+        SECONDARY_INDEXES.with(|indexes| {
+            assert_eq!(indexes.borrow().by_price, BTreeSet::from([(700, 0)]));
+        });
+    }
+    
+    #[test]
+    fn purchase_leaves_indexes_consistent() {
+        let (alice, bob) = (principal(1), principal(2));
+        add_land(land(0, alice, 0, LandType::Agricultural));
+        add_land(land(1, alice, 100, LandType::Agricultural));
+        list(0, alice, 500);
+        list(1, alice, 900);
+        
+        set_land_owner(0, bob, 1).expect("land 0 exists");
+        
+        assert_consistent();
+        assert_eq!(owned_by(alice), HashSet::from([1]));
+        assert_eq!(owned_by(bob), HashSet::from([0]));
+        SECONDARY_INDEXES.with(|indexes| {
+            let indexes = indexes.borrow();
+            assert_eq!(indexes.by_price, BTreeSet::from([(900, 1)]));
+            assert_eq!(indexes.for_sale_by_type.get(&LandType::Agricultural), Some(&BTreeSet::from([1])));
+        });
+    }
+    
+    #[test]
+    fn invariant_check_reports_and_rebuild_repairs_drift() {
+        let alice = principal(1);
+        add_land(land(0, alice, 0, LandType::Entertainment));
+        list(0, alice, 500);
+        SECONDARY_INDEXES.with(|indexes| indexes.borrow_mut().by_price.clear());
+        
+        assert!(collect_invariant_issues().iter().any(|issue| issue.store == "SECONDARY_INDEXES"));
+        
+        rebuild_secondary_indexes();
+        
+        assert_consistent();
+    }
+    
+    #[test]
+    fn register_list_and_buy_through_the_endpoints() {
+        let (alice, bob) = (principal(1), principal(2));
+        let land_id = register(alice, 0);
+        
+        as_caller(alice);
+        list_for_sale(land_id, 700).unwrap();
+        as_caller(bob);
+        buy_land(land_id, current_version(land_id)).unwrap();
+        
+        assert_eq!(get_land_owner(land_id), Some(bob));
+        assert!(get_marketplace_listing(land_id).is_none());
+        assert_eq!(owned_by(bob), HashSet::from([land_id]));
+        let sale = TRANSACTION_HISTORY.with(|history| history.borrow().last().cloned()).unwrap();
+        assert_eq!((sale.from, sale.to, sale.price), (alice, bob, Some(700)));
+        assert_consistent();
+    }
+    
+    #[test]
+    fn a_stale_listing_version_cannot_be_bought() {
+        let (alice, bob) = (principal(1), principal(2));
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        list_for_sale(land_id, 700).unwrap();
+        let seen = current_version(land_id);
+        
+        system::advance_time(1);
+        list_for_sale(land_id, 9_000).unwrap();
+        as_caller(bob);
+        
+        assert!(matches!(buy_land(land_id, seen), Err(LandRegistryError::ListingChanged)));
+        assert_eq!(get_land_owner(land_id), Some(alice));
+        assert_consistent();
+    }
+    
+    #[test]
+    fn sellers_cannot_buy_their_own_listing() {
+        let alice = principal(1);
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        list_for_sale(land_id, 700).unwrap();
+        
+        assert!(matches!(buy_land(land_id, current_version(land_id)), Err(LandRegistryError::InvalidInput)));
+        assert!(get_marketplace_listing(land_id).is_some());
+    }
+    
+    #[test]
+    fn free_space_suggestions_respect_zoning() {
+        let alice = principal(1);
+        add_district(1, 0, 100, LandType::Commercial);
+        let dimensions = Dimensions { width: 10, height: 10, depth: 1 };
+        let region = (Coordinates { x: 0, y: 0, z: 0 }, Coordinates { x: 300, y: 20, z: 1 });
+        
+        let suggestions = find_free_space(dimensions.clone(), LandType::Residential, region.clone(), 5).unwrap();
+        assert!(!suggestions.is_empty());
+        assert!(suggestions.iter().all(|origin| origin.x >= 100));
+        
+        as_caller(alice);
+        for origin in suggestions {
+            let registered = register_land(LandRegistration {
+                coordinates: origin,
+                dimensions: dimensions.clone(),
+                land_type: LandType::Residential,
+                description: "Suggested plot".to_string(),
+                metadata: None,
+            });
+            assert!(registered.is_ok(), "suggestion rejected: {:?}", registered);
+        }
+        
+        let commercial = find_free_space(dimensions, LandType::Commercial, region, 1).unwrap();
+        assert_eq!(commercial, vec![Coordinates { x: 0, y: 0, z: 0 }]);
+    }
+    
+    #[test]
+    fn removal_cancels_pending_rezoning_with_the_removal_reason() {
+        let (alice, moderator) = (principal(1), principal(9));
+        grant(moderator, Role::Moderator);
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        let request_id = request_land_type_change(land_id, LandType::Commercial, "Opening a shop".to_string()).unwrap();
+        
+        as_caller(moderator);
+        remove_land(land_id, "Spam listing".to_string()).unwrap();
+        
+        let request = get_rezoning_request(request_id).unwrap();
+        assert_eq!(request.status, RezoningStatus::Cancelled);
+        assert_eq!(request.resolution_note.as_deref(), Some("Parcel removed: Spam listing"));
+    }
+    
+    #[test]
+    fn a_held_parcel_cannot_be_rezoned() {
+        let (alice, moderator, officer) = (principal(1), principal(9), principal(8));
+        grant(moderator, Role::Moderator);
+        grant(officer, Role::ZoningOfficer);
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        let request_id = request_land_type_change(land_id, LandType::Commercial, "Opening a shop".to_string()).unwrap();
+        as_caller(moderator);
+        place_hold(land_id, HoldKind::Freeze, "Under investigation".to_string(), None, None).unwrap();
+        
+        as_caller(officer);
+        assert!(matches!(approve_rezoning_request(request_id, None), Err(LandRegistryError::LandHeld)));
+        assert_eq!(get_land(land_id).unwrap().land_type, LandType::Residential);
+        assert_eq!(get_rezoning_request(request_id).unwrap().status, RezoningStatus::Pending);
+    }
+    
+    #[test]
+    fn a_governance_vote_can_approve_rezoning() {
+        let alice = principal(1);
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        let request_id = request_land_type_change(land_id, LandType::Commercial, "Opening a shop".to_string()).unwrap();
+        
+        execute_governance_action(GovernanceAction::ApproveRezoning { request_id }).unwrap();
+        
+        assert_eq!(get_land(land_id).unwrap().land_type, LandType::Commercial);
+        assert_eq!(get_rezoning_request(request_id).unwrap().status, RezoningStatus::Approved);
+        assert_consistent();
+    }
+    
+    #[test]
+    fn a_passed_role_vote_still_needs_admin_approval() {
+        let (alice, bob, admin) = (principal(1), principal(2), principal(3));
+        grant(admin, Role::SuperAdmin);
+        register(alice, 0);
+        system::advance_time(1);
+        
+        as_caller(alice);
+        let proposal_id = submit_governance_proposal(
+            "Make bob a moderator".to_string(),
+            String::new(),
+            GovernanceAction::GrantRole { principal: bob, role: Role::Moderator },
+        ).unwrap();
+        vote_on_proposal(proposal_id, true).unwrap();
+        system::advance_time(get_governance_config().voting_period_ns + 1);
+        process_governance_deadlines();
+        
+        assert_eq!(get_governance_proposal(proposal_id).unwrap().status, GovernanceProposalStatus::Executed);
+        assert!(get_roles(bob).is_empty());
+        let pending = ADMIN_PROPOSALS.with(|proposals| proposals.borrow().values().next().cloned()).unwrap();
+        assert_eq!(pending.proposer, system::id());
+        assert!(pending.approvals.is_empty());
+        
+        as_caller(admin);
+        assert!(matches!(approve_admin_action(pending.id), Ok(AdminProposalStatus::Executed)));
+        assert_eq!(get_roles(bob), vec![Role::Moderator]);
+    }
+    
+    #[test]
+    fn world_bounds_may_not_cut_through_a_parcel() {
+        register(principal(1), 0);
+        let bounds = |max_x| GovernanceAction::SetWorldBounds {
+            world_min: Coordinates { x: -100, y: -100, z: -100 },
+            world_max: Coordinates { x: max_x, y: 100, z: 100 },
+        };
+        
+        // The parcel starts at x = 0 and its last cell is at x = 9
+        assert!(matches!(validate_governance_action(&bounds(5)), Err(LandRegistryError::InvalidCoordinates)));
+        assert!(validate_governance_action(&bounds(9)).is_ok());
+    }
+    
+    #[test]
+    fn withdrawals_and_cancellations_still_work_while_paused() {
+        let (alice, operator, guardian, moderator) = (principal(1), principal(2), principal(3), principal(4));
+        grant(moderator, Role::Moderator);
+        let land_id = register(alice, 0);
+        
+        as_caller(alice);
+        list_for_sale(land_id, 500).unwrap();
+        grant_operator(land_id, operator, vec![OperatorPermission::EditMetadata], None).unwrap();
+        let rezoning_id = request_land_type_change(land_id, LandType::Commercial, "Opening a shop".to_string()).unwrap();
+        set_guardians(vec![guardian], 1, MIN_INACTIVITY_PERIOD_NS, MIN_CHALLENGE_PERIOD_NS).unwrap();
+        system::advance_time(MIN_INACTIVITY_PERIOD_NS);
+        as_caller(guardian);
+        let recovery_id = start_recovery(alice, guardian).unwrap();
+        
+        as_caller(moderator);
+        pause(PauseScope::All, "Incident".to_string()).unwrap();
+        
+        // Anything that changes ownership or adds commitments is refused...
+        as_caller(alice);
+        assert!(matches!(list_for_sale(land_id, 600), Err(LandRegistryError::Paused)));
+        // ...but owners can always back out of what they already started
+        remove_from_sale(land_id).unwrap();
+        revoke_operator(land_id, operator).unwrap();
+        cancel_rezoning_request(rezoning_id).unwrap();
+        cancel_recovery(recovery_id).unwrap();
+        
+        assert!(get_marketplace_listing(land_id).is_none());
+        assert_eq!(get_rezoning_request(rezoning_id).unwrap().status, RezoningStatus::Cancelled);
+        assert_eq!(get_land_owner(land_id), Some(alice));
+    }
+    
+    #[test]
+    fn a_hold_on_a_missing_parcel_does_not_use_up_an_id() {
+        let moderator = principal(4);
+        grant(moderator, Role::Moderator);
+        let land_id = register(principal(1), 0);
+        
+        as_caller(moderator);
+        assert!(matches!(place_hold(99, HoldKind::Freeze, "Audit".to_string(), None, None), Err(LandRegistryError::LandNotFound)));
+        assert_eq!(place_hold(land_id, HoldKind::Freeze, "Audit".to_string(), None, None).unwrap(), 0);
+        
+        as_caller(principal(1));
+        assert!(matches!(list_for_sale(land_id, 500), Err(LandRegistryError::LandHeld)));
+        as_caller(moderator);
+        release_hold(land_id, 0).unwrap();
+        assert!(get_land(land_id).unwrap().holds.is_none());
+    }
+    
+    #[test]
+    fn expired_holds_are_pruned() {
+        let moderator = principal(4);
+        grant(moderator, Role::Moderator);
+        let (first, second) = (register(principal(1), 0), register(principal(1), 20));
+        
+        as_caller(moderator);
+        let expires_at = Some(system::time() + 100);
+        place_hold(first, HoldKind::Freeze, "Audit".to_string(), None, expires_at).unwrap();
+        place_hold(second, HoldKind::Freeze, "Audit".to_string(), None, expires_at).unwrap();
+        let lasting = place_hold(second, HoldKind::Freeze, "Court order".to_string(), None, None).unwrap();
+        system::advance_time(100);
+        
+        release_expired_holds();
+        
+        assert!(get_land(first).unwrap().holds.is_none());
+        let remaining = get_land(second).unwrap().holds.unwrap();
+        assert_eq!(remaining.iter().map(|hold| hold.id).collect::<Vec<_>>(), vec![lasting]);
+        let expired_releases = AUDIT_LOG.with(|log| {
+            log.borrow().iter()
+                .filter(|entry| entry.action == AuditAction::HoldReleased && entry.actor == system::id())
+                .count()
+        });
+        assert_eq!(expired_releases, 2);
+    }
+    
+    #[test]
+    fn a_forced_transfer_waits_out_a_transfer_pause() {
+        let (alice, carol, first, second) = (principal(1), principal(2), principal(3), principal(4));
+        grant(first, Role::SuperAdmin);
+        grant(second, Role::SuperAdmin);
+        APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow_mut() = 2);
+        let land_id = register(alice, 0);
+        
+        as_caller(carol);
+        let dispute_id = open_dispute(land_id, "This plot was sold to me".to_string()).unwrap();
+        as_caller(first);
+        place_hold(land_id, HoldKind::Dispute, "Under dispute".to_string(), Some(dispute_id), None).unwrap();
+        let proposal_id = propose_admin_action(AdminAction::ForceTransfer { dispute_id, new_owner: carol, note: None }).unwrap();
+        pause(PauseScope::Transfers, "Incident".to_string()).unwrap();
+        
+        as_caller(second);
+        assert!(matches!(approve_admin_action(proposal_id), Ok(AdminProposalStatus::Pending)));
+        assert_eq!(get_land_owner(land_id), Some(alice));
+        assert_eq!(get_dispute(dispute_id).unwrap().status, DisputeStatus::Open);
+        
+        unpause(PauseScope::Transfers).unwrap();
+        
+        assert_eq!(get_land_owner(land_id), Some(carol));
+        assert!(get_land(land_id).unwrap().holds.is_none());
+        assert_eq!(get_dispute(dispute_id).unwrap().status, DisputeStatus::Resolved);
+        let status = ADMIN_PROPOSALS.with(|proposals| proposals.borrow().get(&proposal_id).map(|proposal| proposal.status.clone()));
+        assert_eq!(status, Some(AdminProposalStatus::Executed));
+        assert_consistent();
+    }
+    
+    #[test]
+    fn an_operator_with_accept_offers_can_sell_to_a_bidder() {
+        let (alice, manager, clerk, bob, carol) = (principal(1), principal(2), principal(3), principal(4), principal(5));
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        grant_operator(land_id, manager, vec![OperatorPermission::AcceptOffers], None).unwrap();
+        grant_operator(land_id, clerk, vec![OperatorPermission::ManageListing], None).unwrap();
+        
+        as_caller(manager);
+        assert!(matches!(make_offer(land_id, 100), Err(LandRegistryError::Unauthorized)));
+        as_caller(bob);
+        let accepted = make_offer(land_id, 900).unwrap();
+        assert!(matches!(make_offer(land_id, 950), Err(LandRegistryError::InvalidInput)));
+        as_caller(carol);
+        make_offer(land_id, 800).unwrap();
+        assert_eq!(get_offers_for_land(land_id, page(10, None, None)).total_count, 2);
+        
+        as_caller(clerk);
+        assert!(matches!(accept_offer(accepted), Err(LandRegistryError::Unauthorized)));
+        as_caller(manager);
+        accept_offer(accepted).unwrap();
+        
+        assert_eq!(get_land_owner(land_id), Some(bob));
+        assert_eq!(get_offers_for_land(land_id, page(10, None, None)).total_count, 0);
+        assert!(get_land_operators(land_id).is_empty());
+        let sale = TRANSACTION_HISTORY.with(|history| history.borrow().last().cloned()).unwrap();
+        assert_eq!((sale.from, sale.to, sale.price), (alice, bob, Some(900)));
+        assert_consistent();
+    }
+    
+    #[test]
+    fn offers_lapse_when_the_parcel_changes_hands() {
+        let (alice, bob, carol) = (principal(1), principal(2), principal(3));
+        let land_id = register(alice, 0);
+        as_caller(carol);
+        let offer_id = make_offer(land_id, 300).unwrap();
+        
+        as_caller(alice);
+        list_for_sale(land_id, 500).unwrap();
+        as_caller(bob);
+        buy_land(land_id, current_version(land_id)).unwrap();
+        
+        as_caller(bob);
+        assert!(matches!(accept_offer(offer_id), Err(LandRegistryError::RequestNotFound)));
+        as_caller(carol);
+        assert!(matches!(withdraw_offer(offer_id), Err(LandRegistryError::RequestNotFound)));
+        
+        // A fresh offer to the new owner can be withdrawn by its bidder only
+        let offer_id = make_offer(land_id, 400).unwrap();
+        as_caller(alice);
+        assert!(matches!(withdraw_offer(offer_id), Err(LandRegistryError::Unauthorized)));
+        as_caller(carol);
+        withdraw_offer(offer_id).unwrap();
+        assert_eq!(get_offers_for_land(land_id, page(10, None, None)).total_count, 0);
+    }
+    
+    #[test]
+    fn any_owner_call_during_the_challenge_keeps_the_parcels() {
+        let (alice, guardian, bob) = (principal(1), principal(2), principal(3));
+        let land_id = register(alice, 0);
+        let bobs = register(bob, 20);
+        as_caller(alice);
+        set_guardians(vec![guardian], 1, MIN_INACTIVITY_PERIOD_NS, MIN_CHALLENGE_PERIOD_NS).unwrap();
+        system::advance_time(MIN_INACTIVITY_PERIOD_NS);
+        
+        as_caller(guardian);
+        let request_id = start_recovery(alice, guardian).unwrap();
+        
+        // Opening a dispute used to leave the owner looking inactive
+        system::advance_time(1);
+        as_caller(alice);
+        open_dispute(bobs, "Our fence is on the wrong side".to_string()).unwrap();
+        system::advance_time(MIN_CHALLENGE_PERIOD_NS);
+        execute_due_recoveries();
+        
+        assert_eq!(get_land_owner(land_id), Some(alice));
+        let status = RECOVERY_REQUESTS.with(|requests| requests.borrow().get(&request_id).map(|request| request.status.clone()));
+        assert_eq!(status, Some(RecoveryStatus::Cancelled));
+    }
+    
+    // The witness for `land_id` in `section` must hash to the published root
+    fn assert_witness_matches_root(section: &[u8], land_id: LandId) {
+        let key = land_id.to_be_bytes();
+        let witness = CERTIFIED_TREE.with(|tree| {
+            tree.borrow().nested_witness(section, |entries| entries.witness(&key))
+        });
+        assert_eq!(witness.digest().to_vec(), system::certified_data());
+    }
+    
+    #[test]
+    fn certified_data_tracks_owners_and_prices() {
+        let (alice, bob) = (principal(1), principal(2));
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        list_for_sale(land_id, 700).unwrap();
+        
+        let listing = get_marketplace_listing_certified(land_id);
+        assert_eq!((listing.land_id, listing.price), (land_id, Some(700)));
+        assert_witness_matches_root(CERTIFIED_LISTINGS_LABEL, land_id);
+        
+        as_caller(bob);
+        buy_land(land_id, current_version(land_id)).unwrap();
+        
+        assert_eq!(get_land_owner_certified(land_id).owner, Some(bob));
+        assert_eq!(get_marketplace_listing_certified(land_id).price, None);
+        assert_witness_matches_root(CERTIFIED_LANDS_LABEL, land_id);
+        // Absence of the withdrawn listing is provable too
+        assert_witness_matches_root(CERTIFIED_LISTINGS_LABEL, land_id);
+        
+        let root = system::certified_data();
+        rebuild_certified_data();
+        assert_eq!(system::certified_data(), root);
+    }
+    
+    #[test]
+    fn snapshot_validation_grandfathers_zoning_but_refuses_overlaps() {
+        let alice = principal(1);
+        let district = District {
+            id: 1,
+            name: "Harbour".to_string(),
+            min_coords: Coordinates { x: -100, y: -100, z: -10 },
+            max_coords: Coordinates { x: 100, y: 100, z: 10 },
+            zoning: ZoningRules { allowed_land_types: vec![LandType::Residential], max_parcel_area: None, max_build_height: None },
+            created_at: 0,
+            last_updated: 0,
+        };
+        let parameters = RegistryParameters::default();
+        let schemas = HashMap::new();
+        
+        let lands = vec![land(0, alice, 0, LandType::Industrial), land(1, alice, 20, LandType::Residential)];
+        let validation = validate_snapshot_lands(&lands, std::slice::from_ref(&district), &schemas, &parameters);
+        assert!(validation.errors.is_empty(), "unexpected errors: {:?}", validation.errors);
+        assert_eq!(validation.warnings.len(), 1);
+        assert_eq!(validation.warnings[0].land_id, Some(0));
+        
+        let lands = vec![land(0, alice, 0, LandType::Residential), land(1, alice, 5, LandType::Residential)];
+        let validation = validate_snapshot_lands(&lands, &[district], &schemas, &parameters);
+        assert_eq!(validation.errors.len(), 1);
+        assert_eq!(validation.errors[0].land_id, Some(1));
+    }
+    
+    #[test]
+    fn a_refused_restore_leaves_the_registry_untouched() {
+        let (admin, alice) = (principal(1), principal(2));
+        grant(admin, Role::SuperAdmin);
+        let land_id = register(alice, 0);
+        as_caller(alice);
+        list_for_sale(land_id, 500).unwrap();
+        
+        let mut outside = land(7, alice, 0, LandType::Residential);
+        outside.coordinates.x = i32::MAX;
+        let snapshot = vec![
+            land(5, alice, 100, LandType::Residential),
+            land(5, alice, 200, LandType::Residential),
+            outside,
+        ];
+        
+        as_caller(alice);
+        assert!(matches!(validate_snapshot(snapshot.clone()), Err(LandRegistryError::Unauthorized)));
+        as_caller(admin);
+        let validation = validate_snapshot(snapshot.clone()).unwrap();
+        let flagged: BTreeSet<Option<LandId>> = validation.errors.iter().map(|issue| issue.land_id).collect();
+        assert_eq!(flagged, BTreeSet::from([Some(5), Some(7)]));
+        
+        assert!(matches!(restore_lands(snapshot), Err(LandRegistryError::InvalidSnapshot)));
+        assert_eq!(get_land_owner(land_id), Some(alice));
+        assert!(get_marketplace_listing(land_id).is_some());
+        assert_consistent();
+    }
+    
+    #[test]
+    fn approval_threshold_is_not_capped_by_eligible_approvers() {
+        ROLES.with(|roles| {
+            let mut roles = roles.borrow_mut();
+            roles.insert(principal(1), BTreeSet::from([Role::SuperAdmin]));
+            roles.insert(principal(2), BTreeSet::from([Role::SuperAdmin]));
+            roles.insert(principal(3), BTreeSet::from([Role::Moderator]));
+        });
+        APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow_mut() = 2);
+        
+        assert_eq!(required_approvals(), 2);
+        assert_eq!(eligible_approvers(Role::Moderator), 3);
+        assert!(ensure_super_admins_remain(Role::Moderator).is_ok());
+        assert!(matches!(ensure_super_admins_remain(Role::SuperAdmin), Err(LandRegistryError::InsufficientApprovers)));
+        
+        let revoke = AdminAction::RevokeRole { principal: principal(2), role: Role::SuperAdmin };
+        assert!(is_destructive(&revoke));
+        assert!(matches!(precheck_admin_action(&revoke), Err(LandRegistryError::InsufficientApprovers)));
+        assert!(matches!(
+            precheck_admin_action(&AdminAction::SetApprovalThreshold(3)),
+            Err(LandRegistryError::InsufficientApprovers)
+        ));
+    }
+    
+    #[test]
+    fn parcels_registered_after_a_proposal_carry_no_power() {
+        let alice = principal(1);
+        add_land(land(0, alice, 0, LandType::Residential));
+        add_land(LandInfo { created_at: 500, ..land(1, alice, 20, LandType::Residential) });
+        
+        let proposal_created_at = 100;
+        assert_eq!(voter_lands(&alice, proposal_created_at), vec![(0, 100)]);
+        assert_eq!(total_voting_power(proposal_created_at), 100);
+        assert_eq!(total_voting_power(Timestamp::MAX), 200);
+    }
+    
+    #[test]
+    fn id_ordered_queries_walk_every_page_from_the_cursor() {
+        for id in [4, 1, 3, 0, 2] {
+            add_district(id, (id as i32) * 100, (id as i32) * 100 + 50, LandType::Residential);
+        }
+        
+        let first = get_districts(page(2, None, None));
+        assert_eq!(first.total_count, 5);
+        assert_eq!(first.items.iter().map(|district| district.id).collect::<Vec<_>>(), vec![0, 1]);
+        
+        let ascending = collect_pages(2, None, get_districts);
+        assert_eq!(ascending.iter().map(|district| district.id).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        let descending = collect_pages(2, Some(SortDirection::Descending), get_districts);
+        assert_eq!(descending.iter().map(|district| district.id).collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+        
+        let last = get_districts(page(5, None, None));
+        assert!(last.next_cursor.is_none());
+    }
+    
+    #[test]
+    fn filtered_log_queries_count_and_page_only_matches() {
+        let alice = principal(1);
+        let (kept, sold) = (register(alice, 0), register(alice, 20));
+        for (index, price) in [300, 400, 500].into_iter().enumerate() {
+            let buyer = principal(10 + index as u8);
+            let seller = get_land_owner(sold).unwrap();
+            as_caller(seller);
+            list_for_sale(sold, price).unwrap();
+            as_caller(buyer);
+            buy_land(sold, current_version(sold)).unwrap();
+        }
+        
+        let prices = collect_pages(2, None, |request| get_price_history(sold, request));
+        assert_eq!(prices.iter().map(|(_, price)| *price).collect::<Vec<_>>(), vec![300, 400, 500]);
+        assert_eq!(get_price_history(sold, page(1, None, None)).total_count, 3);
+        assert_eq!(get_price_history(kept, page(10, None, None)).total_count, 0);
+        
+        let newest_first = collect_pages(1, Some(SortDirection::Descending), |request| get_price_history(sold, request));
+        assert_eq!(newest_first.iter().map(|(_, price)| *price).collect::<Vec<_>>(), vec![500, 400, 300]);
+    }
+    
+    #[test]
+    fn sorted_pages_match_a_full_sort() {
+        let keyed: Vec<(u64, u64, u64)> = (0..20).map(|id| ((id * 7) % 5, id, id)).collect();
+        let mut expected = keyed.clone();
+        expected.sort();
+        
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let next = paginate(keyed.clone(), &page(3, cursor, Some(SortDirection::Ascending)));
+            assert_eq!(next.total_count, 20);
+            seen.extend(next.items);
+            match next.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+        assert_eq!(seen, expected.into_iter().map(|(_, _, item)| item).collect::<Vec<_>>());
+    }
+    
+    #[test]
+    fn restoring_over_a_listed_parcel_withdraws_the_listing() {
+        let (alice, bob, carol) = (principal(1), principal(2), principal(3));
+        let land_id = register(alice, 0);
+        let gone = register(alice, 20);
+        as_caller(alice);
+        list_for_sale(land_id, 700).unwrap();
+        list_for_sale(gone, 800).unwrap();
+        let version = current_version(land_id);
+        
+        // The snapshot gives the listed parcel to bob and drops the other one
+        replace_lands(vec![land(land_id, bob, 0, LandType::Residential)], alice).unwrap();
+        
+        assert!(get_marketplace_listing(land_id).is_none());
+        assert!(get_marketplace_listing(gone).is_none());
+        as_caller(carol);
+        assert!(buy_land(land_id, version).is_err());
+        assert_eq!(get_land_owner(land_id), Some(bob));
+        assert_consistent();
+    }
+    
+    #[test]
+    fn approvals_execute_once_the_threshold_is_met() {
+        let (first, second, third) = (principal(1), principal(2), principal(3));
+        for admin in [first, second, third] {
+            grant(admin, Role::SuperAdmin);
+        }
+        APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow_mut() = 2);
+        let land_id = register(principal(9), 0);
+        
+        // Direct removal needs approval once the threshold is above one
+        as_caller(first);
+        assert!(matches!(remove_land(land_id, "Spam".to_string()), Err(LandRegistryError::ApprovalRequired)));
+        let proposal_id = propose_admin_action(AdminAction::RemoveLand { land_id, reason: "Spam".to_string() }).unwrap();
+        assert!(get_land(land_id).is_some());
+        assert!(matches!(approve_admin_action(proposal_id), Err(LandRegistryError::InvalidInput)));
+        
+        as_caller(second);
+        assert!(matches!(approve_admin_action(proposal_id), Ok(AdminProposalStatus::Executed)));
+        assert!(get_land(land_id).is_none());
+        as_caller(third);
+        assert!(matches!(approve_admin_action(proposal_id), Err(LandRegistryError::InvalidInput)));
+    }
+    
+    #[test]
+    fn a_late_approval_expires_the_proposal_without_running_it() {
+        let (first, second) = (principal(1), principal(2));
+        grant(first, Role::SuperAdmin);
+        grant(second, Role::SuperAdmin);
+        APPROVAL_THRESHOLD.with(|threshold| *threshold.borrow_mut() = 2);
+        let land_id = register(principal(9), 0);
+        
+        as_caller(first);
+        let proposal_id = propose_admin_action(AdminAction::RemoveLand { land_id, reason: "Spam".to_string() }).unwrap();
+        system::advance_time(ADMIN_PROPOSAL_WINDOW_NS + 1);
+        
+        as_caller(second);
+        assert!(matches!(approve_admin_action(proposal_id), Err(LandRegistryError::ProposalExpired)));
+        assert!(get_land(land_id).is_some());
+        let proposal = ADMIN_PROPOSALS.with(|proposals| proposals.borrow().get(&proposal_id).cloned()).unwrap();
+        assert_eq!(proposal.status, AdminProposalStatus::Expired);
+        assert_eq!(proposal.approvals, vec![first]);
+        let approved = AUDIT_LOG.with(|log| {
+            log.borrow().iter().any(|entry| matches!(entry.action, AuditAction::AdminActionApproved))
+        });
+        assert!(!approved);
+    }
+    
+    #[test]
+    fn only_super_admins_are_admins() {
+        let (owner, moderator, nobody) = (principal(1), principal(2), principal(3));
+        grant(owner, Role::SuperAdmin);
+        grant(moderator, Role::Moderator);
+        
+        assert!(is_admin(owner));
+        assert!(!is_admin(moderator));
+        as_caller(moderator);
+        assert_eq!(get_my_roles(), vec![Role::Moderator]);
+        
+        // Every role holder can still see what is waiting for approval
+        assert!(get_admin_proposals(true, page(10, None, None)).is_ok());
+        as_caller(nobody);
+        assert!(get_my_roles().is_empty());
+        assert!(matches!(get_admin_proposals(true, page(10, None, None)), Err(LandRegistryError::Unauthorized)));
+    }
+}
+
 // Export candid interface
 ic_cdk::export_candid!();
\ No newline at end of file
@@ -13,6 +13,7 @@ import {
   LineChart,
   Line
 } from 'recharts';
+import { fetchAllPages } from '../utils/api';
 
 export default function Analytics({ actor }) {
   const [lands, setLands] = useState([]);
@@ -25,7 +26,7 @@ export default function Analytics({ actor }) {
   async function fetchLands() {
     try {
       setLoading(true);
-      const data = await actor.get_all_lands();
+      const data = await fetchAllPages(page => actor.get_all_lands(page));
       setLands(data);
     } catch (err) {
       console.error('Analytics fetch error', err);
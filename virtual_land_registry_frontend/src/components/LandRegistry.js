@@ -1,6 +1,7 @@
 import React, { useState, useEffect } from 'react';
 import { useNavigate } from 'react-router-dom';
 import LandCard from './LandCard';
+import { fetchAllPages } from '../utils/api';
 
 export default function LandRegistry({ actor, isAuthenticated, principal }) {
   const [lands, setLands]       = useState([]);
@@ -15,7 +16,7 @@ export default function LandRegistry({ actor, isAuthenticated, principal }) {
   async function fetchLands() {
     try {
       setLoading(true);
-      const all = await actor.get_all_lands();
+      const all = await fetchAllPages(page => actor.get_all_lands(page));
       setLands(all);
     } catch (err) {
       console.error(err);
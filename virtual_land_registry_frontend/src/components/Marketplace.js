@@ -1,4 +1,5 @@
 import React, { useState, useEffect } from 'react';
+import { fetchAllPages, emptySearchFilters } from '../utils/api';
 
 export default function Marketplace({ actor, isAuthenticated, principal }) {
   const [listings, setListings]       = useState([]);
@@ -21,7 +22,7 @@ export default function Marketplace({ actor, isAuthenticated, principal }) {
   async function loadListings() {
     try {
       setLoading(true);
-      const all = await actor.get_marketplace_listings();
+      const all = await fetchAllPages(page => actor.get_marketplace_listings(page));
       setListings(all);
     } catch (err) {
       console.error(err);
@@ -35,14 +36,13 @@ export default function Marketplace({ actor, isAuthenticated, principal }) {
     try {
       setLoading(true);
       const f = {
+        ...emptySearchFilters(),
         land_type:  filters.land_type ? [{ [filters.land_type]: null }] : [],
         min_price:  filters.min_price ? [BigInt(filters.min_price)] : [],
         max_price:  filters.max_price ? [BigInt(filters.max_price)] : [],
-        coordinates_range: [],
-        min_area:   filters.min_area ? [Number(filters.min_area)] : [],
-        features:   []
+        min_area:   filters.min_area ? [Number(filters.min_area)] : []
       };
-      const res = await actor.search_marketplace(f);
+      const res = await fetchAllPages(page => actor.search_marketplace(f, page));
       setListings(res);
     } catch (err) {
       console.error(err);
@@ -51,14 +51,19 @@ export default function Marketplace({ actor, isAuthenticated, principal }) {
     }
   }
 
-  async function buy(landId) {
+  // The listing version shown to the buyer is sent along, so the sale is refused
+  // if the parcel or its price changed in the meantime
+  async function buy(listing) {
     if (!isAuthenticated) return alert('Connect wallet');
     try {
       setLoading(true);
-      const res = await actor.buy_land(landId);
+      const res = await actor.buy_land(listing.land_id, listing.version);
       if (res.Ok !== undefined) {
         alert('Purchased');
         loadListings();
+      } else if (res.Err.ListingChanged !== undefined) {
+        alert('This listing changed since it was loaded. Please review it and try again.');
+        loadListings();
       } else {
         alert(`Error: ${Object.keys(res.Err)[0]}`);
       }
@@ -140,7 +145,7 @@ export default function Marketplace({ actor, isAuthenticated, principal }) {
               <p className="mt-2">{item.land_info.description}</p>
               <div className="mt-4 text-right">
                 {isAuthenticated && principal.toString() !== item.seller.toString() ? (
-                  <button onClick={() => buy(item.land_id)} className="btn btn-primary">
+                  <button onClick={() => buy(item)} className="btn btn-primary">
                     Buy Now
                   </button>
                 ) : (
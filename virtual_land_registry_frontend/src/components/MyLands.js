@@ -1,4 +1,5 @@
 import React, { useState, useEffect } from 'react';
+import { fetchAllPages } from '../utils/api';
 
 export default function MyLands({ actor, principal }) {
   const [lands, setLands]       = useState([]);
@@ -15,7 +16,7 @@ export default function MyLands({ actor, principal }) {
   async function loadMy() {
     try {
       setLoading(true);
-      const res = await actor.get_lands_by_owner(principal);
+      const res = await fetchAllPages(page => actor.get_lands_by_owner(principal, page));
       setLands(res);
     } catch (err) {
       console.error(err);
@@ -1,135 +1,1102 @@
 export const idlFactory = ({ IDL }) => {
-  const Coordinates = IDL.Record({
-    'x' : IDL.Int32,
-    'y' : IDL.Int32,
-    'z' : IDL.Int32,
+  const LandRegistryError = IDL.Variant({
+    'InvalidCoordinates' : IDL.Null,
+    'OwnershipError' : IDL.Null,
+    'Paused' : IDL.Null,
+    'ZoningViolation' : IDL.Null,
+    'OwnerActive' : IDL.Null,
+    'InvalidInput' : IDL.Null,
+    'ApprovalRequired' : IDL.Null,
+    'InvalidAttribute' : IDL.Null,
+    'LandNotForSale' : IDL.Null,
+    'InsufficientApprovers' : IDL.Null,
+    'ListingChanged' : IDL.Null,
+    'LandHeld' : IDL.Null,
+    'ProposalExpired' : IDL.Null,
+    'Unauthorized' : IDL.Null,
+    'DistrictNotFound' : IDL.Null,
+    'LastSuperAdmin' : IDL.Null,
+    'RequestNotFound' : IDL.Null,
+    'LandNotFound' : IDL.Null,
+    'InvalidSnapshot' : IDL.Null,
+    'InvalidDimensions' : IDL.Null,
+    'InsufficientFunds' : IDL.Null,
+    'LandAlreadyExists' : IDL.Null,
   });
-  const Dimensions = IDL.Record({
-    'width' : IDL.Nat32,
-    'height' : IDL.Nat32,
-    'depth' : IDL.Nat32,
+  const LandRegistryResult_1 = IDL.Variant({
+    'Ok' : IDL.Null,
+    'Err' : LandRegistryError,
+  });
+  const AdminProposalStatus = IDL.Variant({
+    'Failed' : IDL.Null,
+    'Executed' : IDL.Null,
+    'Cancelled' : IDL.Null,
+    'Expired' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const AdminProposalStatusResult = IDL.Variant({
+    'Ok' : AdminProposalStatus,
+    'Err' : LandRegistryError,
+  });
+  const RecoveryStatus = IDL.Variant({
+    'Executed' : IDL.Null,
+    'Cancelled' : IDL.Null,
+    'Challenge' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const RecoveryStatusResult = IDL.Variant({
+    'Ok' : RecoveryStatus,
+    'Err' : LandRegistryError,
+  });
+  const HoldKind = IDL.Variant({
+    'Lien' : IDL.Null,
+    'Dispute' : IDL.Null,
+    'Freeze' : IDL.Null,
+  });
+  const LandHold = IDL.Record({
+    'id' : IDL.Nat64,
+    'placed_at' : IDL.Nat64,
+    'placed_by' : IDL.Principal,
+    'kind' : HoldKind,
+    'dispute_id' : IDL.Opt(IDL.Nat64),
+    'expires_at' : IDL.Opt(IDL.Nat64),
+    'reason' : IDL.Text,
+  });
+  const AttributeValue = IDL.Variant({
+    'Bool' : IDL.Bool,
+    'Enum' : IDL.Text,
+    'Text' : IDL.Text,
+    'Number' : IDL.Int64,
+  });
+  const LandAttribute = IDL.Record({
+    'key' : IDL.Text,
+    'value' : AttributeValue,
+  });
+  const LandMetadata = IDL.Record({
+    'utilities' : IDL.Vec(IDL.Text),
+    'attributes' : IDL.Opt(IDL.Vec(LandAttribute)),
+    'access_roads' : IDL.Vec(IDL.Text),
+    'environment' : IDL.Opt(IDL.Text),
+    'special_features' : IDL.Vec(IDL.Text),
   });
   const LandType = IDL.Variant({
-    'Residential' : IDL.Null,
-    'Commercial' : IDL.Null,
-    'Industrial' : IDL.Null,
     'Agricultural' : IDL.Null,
+    'Commercial' : IDL.Null,
     'Entertainment' : IDL.Null,
     'Mixed' : IDL.Null,
+    'Residential' : IDL.Null,
+    'Industrial' : IDL.Null,
   });
-  const LandMetadata = IDL.Record({
-    'environment' : IDL.Opt(IDL.Text),
-    'special_features' : IDL.Vec(IDL.Text),
-    'access_roads' : IDL.Vec(IDL.Text),
-    'utilities' : IDL.Vec(IDL.Text),
+  const Dimensions = IDL.Record({
+    'height' : IDL.Nat32,
+    'width' : IDL.Nat32,
+    'depth' : IDL.Nat32,
+  });
+  const Coordinates = IDL.Record({
+    'x' : IDL.Int32,
+    'y' : IDL.Int32,
+    'z' : IDL.Int32,
   });
   const LandInfo = IDL.Record({
     'id' : IDL.Nat64,
+    'holds' : IDL.Opt(IDL.Vec(LandHold)),
     'owner' : IDL.Principal,
-    'coordinates' : Coordinates,
-    'dimensions' : Dimensions,
-    'land_type' : LandType,
-    'description' : IDL.Text,
     'metadata' : IDL.Opt(LandMetadata),
-    'created_at' : IDL.Nat64,
+    'description' : IDL.Text,
     'last_updated' : IDL.Nat64,
-  });
-  const LandRegistration = IDL.Record({
-    'coordinates' : Coordinates,
-    'dimensions' : Dimensions,
+    'created_at' : IDL.Nat64,
     'land_type' : LandType,
+    'dimensions' : Dimensions,
+    'coordinates' : Coordinates,
+  });
+  const SnapshotManifest = IDL.Record({
+    'format_version' : IDL.Nat32,
+    'total_bytes' : IDL.Nat64,
+    'created_at' : IDL.Nat64,
+    'chunk_count' : IDL.Nat32,
+    'checksum' : IDL.Vec(IDL.Nat8),
+    'chunk_size' : IDL.Nat32,
+    'snapshot_id' : IDL.Nat64,
+  });
+  const SnapshotManifestResult = IDL.Variant({
+    'Ok' : SnapshotManifest,
+    'Err' : LandRegistryError,
+  });
+  const InvariantIssue = IDL.Record({
     'description' : IDL.Text,
-    'metadata' : IDL.Opt(LandMetadata),
+    'store' : IDL.Text,
+    'land_id' : IDL.Opt(IDL.Nat64),
   });
-  const LandRegistryError = IDL.Variant({
-    'LandNotFound' : IDL.Null,
-    'Unauthorized' : IDL.Null,
-    'LandAlreadyExists' : IDL.Null,
-    'InvalidCoordinates' : IDL.Null,
-    'InvalidDimensions' : IDL.Null,
-    'InsufficientFunds' : IDL.Null,
-    'LandNotForSale' : IDL.Null,
-    'OwnershipError' : IDL.Null,
-    'InvalidInput' : IDL.Null,
+  const InvariantCheckResult = IDL.Variant({
+    'Ok' : IDL.Vec(InvariantIssue),
+    'Err' : LandRegistryError,
+  });
+  const ZoningRules = IDL.Record({
+    'max_build_height' : IDL.Opt(IDL.Nat32),
+    'max_parcel_area' : IDL.Opt(IDL.Nat32),
+    'allowed_land_types' : IDL.Vec(LandType),
+  });
+  const DistrictDefinition = IDL.Record({
+    'min_coords' : Coordinates,
+    'name' : IDL.Text,
+    'max_coords' : Coordinates,
+    'zoning' : ZoningRules,
   });
   const LandRegistryResult = IDL.Variant({
     'Ok' : IDL.Nat64,
     'Err' : LandRegistryError,
   });
-  const LandRegistryResult_1 = IDL.Variant({
-    'Ok' : IDL.Null,
-    'Err' : LandRegistryError,
+  const AttributeKind = IDL.Variant({
+    'Bool' : IDL.Null,
+    'Enum' : IDL.Vec(IDL.Text),
+    'Text' : IDL.Record({ 'max_length' : IDL.Opt(IDL.Nat32) }),
+    'Number' : IDL.Record({
+      'max' : IDL.Opt(IDL.Int64),
+      'min' : IDL.Opt(IDL.Int64),
+    }),
   });
-  const MarketplaceListing = IDL.Record({
-    'land_id' : IDL.Nat64,
-    'seller' : IDL.Principal,
-    'price' : IDL.Nat64,
-    'listed_at' : IDL.Nat64,
-    'land_info' : LandInfo,
+  const AttributeSchema = IDL.Record({
+    'key' : IDL.Text,
+    'kind' : AttributeKind,
+    'label' : IDL.Text,
   });
   const SearchFilters = IDL.Record({
+    'max_area' : IDL.Opt(IDL.Nat32),
+    'features' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'owner' : IDL.Opt(IDL.Principal),
+    'text' : IDL.Opt(IDL.Text),
+    'utilities' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'min_area' : IDL.Opt(IDL.Nat32),
+    'for_sale_only' : IDL.Opt(IDL.Bool),
+    'created_after' : IDL.Opt(IDL.Nat64),
+    'coordinates_range' : IDL.Opt(IDL.Tuple(Coordinates, Coordinates)),
     'land_type' : IDL.Opt(LandType),
-    'min_price' : IDL.Opt(IDL.Nat64),
+    'attributes' : IDL.Opt(IDL.Vec(LandAttribute)),
+    'access_roads' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'environment' : IDL.Opt(IDL.Text),
     'max_price' : IDL.Opt(IDL.Nat64),
-    'coordinates_range' : IDL.Opt(IDL.Tuple(Coordinates, Coordinates)),
-    'min_area' : IDL.Opt(IDL.Nat32),
-    'features' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'created_before' : IDL.Opt(IDL.Nat64),
+    'min_price' : IDL.Opt(IDL.Nat64),
+  });
+  const SortKey = IDL.Variant({
+    'Id' : IDL.Null,
+    'ListedAt' : IDL.Null,
+    'Area' : IDL.Null,
+    'Relevance' : IDL.Null,
+    'Price' : IDL.Null,
+    'CreatedAt' : IDL.Null,
+  });
+  const SortDirection = IDL.Variant({
+    'Descending' : IDL.Null,
+    'Ascending' : IDL.Null,
+  });
+  const PageCursor = IDL.Record({ 'id' : IDL.Nat64, 'sort_value' : IDL.Nat64 });
+  const PageRequest = IDL.Record({
+    'sort_by' : IDL.Opt(SortKey),
+    'direction' : IDL.Opt(SortDirection),
+    'cursor' : IDL.Opt(PageCursor),
+    'limit' : IDL.Opt(IDL.Nat32),
+  });
+  const GeoJsonExport = IDL.Record({
+    'geojson' : IDL.Text,
+    'next_cursor' : IDL.Opt(PageCursor),
+    'total_count' : IDL.Nat64,
+  });
+  const GltfExport = IDL.Record({
+    'glb' : IDL.Vec(IDL.Nat8),
+    'next_cursor' : IDL.Opt(PageCursor),
+    'total_count' : IDL.Nat64,
+  });
+  const SnapshotChunkResult = IDL.Variant({
+    'Ok' : IDL.Vec(IDL.Nat8),
+    'Err' : LandRegistryError,
+  });
+  const FreeSpaceResult = IDL.Variant({
+    'Ok' : IDL.Vec(Coordinates),
+    'Err' : LandRegistryError,
+  });
+  const Role = IDL.Variant({
+    'SupportAgent' : IDL.Null,
+    'Treasurer' : IDL.Null,
+    'SuperAdmin' : IDL.Null,
+    'ZoningOfficer' : IDL.Null,
+    'Moderator' : IDL.Null,
+  });
+  const AdminAction = IDL.Variant({
+    'CommitSnapshotImport' : IDL.Record({ 'checksum' : IDL.Vec(IDL.Nat8) }),
+    'SetApprovalThreshold' : IDL.Nat32,
+    'ForceTransfer' : IDL.Record({
+      'note' : IDL.Opt(IDL.Text),
+      'dispute_id' : IDL.Nat64,
+      'new_owner' : IDL.Principal,
+    }),
+    'RevokeRole' : IDL.Record({ 'principal' : IDL.Principal, 'role' : Role }),
+    'RestoreLands' : IDL.Vec(LandInfo),
+    'GrantRole' : IDL.Record({ 'principal' : IDL.Principal, 'role' : Role }),
+    'RemoveLand' : IDL.Record({ 'land_id' : IDL.Nat64, 'reason' : IDL.Text }),
+  });
+  const AdminProposal = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : AdminProposalStatus,
+    'action' : AdminAction,
+    'failure' : IDL.Opt(LandRegistryError),
+    'required_approvals' : IDL.Nat32,
+    'created_at' : IDL.Nat64,
+    'proposer' : IDL.Principal,
+    'expires_at' : IDL.Nat64,
+    'approvals' : IDL.Vec(IDL.Principal),
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+  });
+  const AdminProposalPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(AdminProposal),
+    'total_count' : IDL.Nat64,
+  });
+  const AdminProposalsResult = IDL.Variant({
+    'Ok' : AdminProposalPage,
+    'Err' : LandRegistryError,
+  });
+  const LandPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(LandInfo),
+    'total_count' : IDL.Nat64,
+  });
+  const AuditAction = IDL.Variant({
+    'MetadataUpdated' : IDL.Null,
+    'RecoveryApproved' : IDL.Null,
+    'DistrictRemoved' : IDL.Null,
+    'AdminActionCancelled' : IDL.Null,
+    'OperatorGranted' : IDL.Null,
+    'RezoningResolved' : IDL.Null,
+    'AttributeDefined' : IDL.Null,
+    'Paused' : IDL.Null,
+    'RoleRevoked' : IDL.Null,
+    'LandRestored' : IDL.Null,
+    'DistrictCreated' : IDL.Null,
+    'RecoveryExecuted' : IDL.Null,
+    'HoldReleased' : IDL.Null,
+    'GovernanceProposalSubmitted' : IDL.Null,
+    'RecoveryStarted' : IDL.Null,
+    'GuardiansUpdated' : IDL.Null,
+    'LandRezoned' : IDL.Null,
+    'HoldPlaced' : IDL.Null,
+    'RezoningRequested' : IDL.Null,
+    'LandForceTransferred' : IDL.Null,
+    'RegistryRepaired' : IDL.Null,
+    'AdminActionApproved' : IDL.Null,
+    'GovernanceProposalResolved' : IDL.Null,
+    'AttributeRemoved' : IDL.Null,
+    'RoleGranted' : IDL.Null,
+    'LandPurged' : IDL.Null,
+    'OfferAccepted' : IDL.Null,
+    'LandSold' : IDL.Null,
+    'AdminActionProposed' : IDL.Null,
+    'DisputeOpened' : IDL.Null,
+    'DisputeEvidenceSubmitted' : IDL.Null,
+    'LandsRestored' : IDL.Null,
+    'DisputeResolved' : IDL.Null,
+    'OfferMade' : IDL.Null,
+    'RecoveryCancelled' : IDL.Null,
+    'DistrictUpdated' : IDL.Null,
+    'SnapshotImported' : IDL.Null,
+    'LandRegistered' : IDL.Null,
+    'OperatorRevoked' : IDL.Null,
+    'Unpaused' : IDL.Null,
+    'LandRemoved' : IDL.Null,
+    'AdminActionResolved' : IDL.Null,
+    'LandDelisted' : IDL.Null,
+    'LandListed' : IDL.Null,
+    'LandTransferred' : IDL.Null,
+    'OfferWithdrawn' : IDL.Null,
+  });
+  const PauseScope = IDL.Variant({
+    'All' : IDL.Null,
+    'Marketplace' : IDL.Null,
+    'Registration' : IDL.Null,
+    'Transfers' : IDL.Null,
+  });
+  const AuditTarget = IDL.Variant({
+    'Land' : IDL.Nat64,
+    'RezoningRequest' : IDL.Nat64,
+    'RecoveryRequest' : IDL.Nat64,
+    'Pause' : PauseScope,
+    'District' : IDL.Nat64,
+    'AdminProposal' : IDL.Nat64,
+    'Dispute' : IDL.Nat64,
+    'GovernanceProposal' : IDL.Nat64,
+    'Attribute' : IDL.Text,
+    'Registry' : IDL.Null,
+    'Principal' : IDL.Principal,
+    'Offer' : IDL.Nat64,
+  });
+  const AuditFilter = IDL.Record({
+    'to' : IDL.Opt(IDL.Nat64),
+    'action' : IDL.Opt(AuditAction),
+    'actor' : IDL.Opt(IDL.Principal),
+    'from' : IDL.Opt(IDL.Nat64),
+    'target' : IDL.Opt(AuditTarget),
+  });
+  const AuditEntry = IDL.Record({
+    'id' : IDL.Nat64,
+    'action' : AuditAction,
+    'actor' : IDL.Principal,
+    'after' : IDL.Opt(IDL.Text),
+    'target' : AuditTarget,
+    'before' : IDL.Opt(IDL.Text),
+    'timestamp' : IDL.Nat64,
+  });
+  const AuditPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(AuditEntry),
+    'total_count' : IDL.Nat64,
+  });
+  const AuditPageResult = IDL.Variant({
+    'Ok' : AuditPage,
+    'Err' : LandRegistryError,
+  });
+  const DisputeStatus = IDL.Variant({
+    'Open' : IDL.Null,
+    'Resolved' : IDL.Null,
+  });
+  const DisputeEvidence = IDL.Record({
+    'content' : IDL.Text,
+    'submitted_at' : IDL.Nat64,
+    'submitted_by' : IDL.Principal,
+  });
+  const DisputeOutcome = IDL.Variant({
+    'Dismissed' : IDL.Null,
+    'ForcedTransfer' : IDL.Principal,
+    'Upheld' : IDL.Null,
+  });
+  const Dispute = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : DisputeStatus,
+    'claimant' : IDL.Principal,
+    'statement' : IDL.Text,
+    'resolution_note' : IDL.Opt(IDL.Text),
+    'opened_at' : IDL.Nat64,
+    'evidence' : IDL.Vec(DisputeEvidence),
+    'respondent' : IDL.Principal,
+    'outcome' : IDL.Opt(DisputeOutcome),
+    'land_id' : IDL.Nat64,
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+    'resolved_by' : IDL.Opt(IDL.Principal),
+  });
+  const DisputePage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(Dispute),
+    'total_count' : IDL.Nat64,
+  });
+  const District = IDL.Record({
+    'id' : IDL.Nat64,
+    'min_coords' : Coordinates,
+    'name' : IDL.Text,
+    'last_updated' : IDL.Nat64,
+    'created_at' : IDL.Nat64,
+    'max_coords' : Coordinates,
+    'zoning' : ZoningRules,
+  });
+  const DistrictPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(District),
+    'total_count' : IDL.Nat64,
+  });
+  const VotingPowerBasis = IDL.Variant({
+    'LandArea' : IDL.Null,
+    'ParcelCount' : IDL.Null,
+  });
+  const GovernanceConfig = IDL.Record({
+    'voting_period_ns' : IDL.Nat64,
+    'voting_power_basis' : VotingPowerBasis,
+    'quorum_bps' : IDL.Nat32,
+    'min_proposer_power' : IDL.Nat64,
+  });
+  const GovernanceProposalStatus = IDL.Variant({
+    'Failed' : IDL.Null,
+    'Open' : IDL.Null,
+    'Rejected' : IDL.Null,
+    'Executed' : IDL.Null,
+  });
+  const GovernanceAction = IDL.Variant({
+    'SetDistrictZoning' : IDL.Record({
+      'district_id' : IDL.Nat64,
+      'zoning' : ZoningRules,
+    }),
+    'ApproveRezoning' : IDL.Record({ 'request_id' : IDL.Nat64 }),
+    'RevokeRole' : IDL.Record({ 'principal' : IDL.Principal, 'role' : Role }),
+    'SetWorldBounds' : IDL.Record({
+      'world_max' : Coordinates,
+      'world_min' : Coordinates,
+    }),
+    'GrantRole' : IDL.Record({ 'principal' : IDL.Principal, 'role' : Role }),
+    'SetGovernanceConfig' : GovernanceConfig,
+  });
+  const GovernanceVote = IDL.Record({
+    'voter' : IDL.Principal,
+    'approve' : IDL.Bool,
+    'timestamp' : IDL.Nat64,
+    'power' : IDL.Nat64,
+  });
+  const GovernanceProposal = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : GovernanceProposalStatus,
+    'title' : IDL.Text,
+    'action' : GovernanceAction,
+    'failure' : IDL.Opt(LandRegistryError),
+    'votes' : IDL.Vec(GovernanceVote),
+    'no_power' : IDL.Nat64,
+    'description' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'voting_ends_at' : IDL.Nat64,
+    'proposer' : IDL.Principal,
+    'yes_power' : IDL.Nat64,
+    'counted_lands' : IDL.Vec(IDL.Nat64),
+    'quorum' : IDL.Nat64,
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+  });
+  const GovernanceProposalPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(GovernanceProposal),
+    'total_count' : IDL.Nat64,
+  });
+  const GuardianConfig = IDL.Record({
+    'updated_at' : IDL.Nat64,
+    'inactivity_period_ns' : IDL.Nat64,
+    'guardians' : IDL.Vec(IDL.Principal),
+    'threshold' : IDL.Nat32,
+    'owner' : IDL.Principal,
+    'challenge_period_ns' : IDL.Nat64,
+  });
+  const OperatorPermission = IDL.Variant({
+    'ManageListing' : IDL.Null,
+    'AcceptOffers' : IDL.Null,
+    'EditMetadata' : IDL.Null,
+  });
+  const OperatorGrant = IDL.Record({
+    'permissions' : IDL.Vec(OperatorPermission),
+    'operator' : IDL.Principal,
+    'granted_at' : IDL.Nat64,
+    'granted_by' : IDL.Principal,
+    'expires_at' : IDL.Opt(IDL.Nat64),
+    'land_id' : IDL.Nat64,
+  });
+  const CertifiedOwner = IDL.Record({
+    'certificate' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'owner' : IDL.Opt(IDL.Principal),
+    'witness' : IDL.Vec(IDL.Nat8),
   });
   const LandStatistics = IDL.Record({
-    'total_lands' : IDL.Nat64,
-    'total_owners' : IDL.Nat64,
     'lands_for_sale' : IDL.Nat64,
     'average_price' : IDL.Opt(IDL.Nat64),
+    'total_owners' : IDL.Nat64,
     'total_transactions' : IDL.Nat64,
+    'total_lands' : IDL.Nat64,
+  });
+  const MarketplaceListing = IDL.Record({
+    'seller' : IDL.Principal,
+    'land_info' : LandInfo,
+    'version' : IDL.Nat64,
+    'price' : IDL.Nat64,
+    'land_id' : IDL.Nat64,
+    'listed_at' : IDL.Nat64,
+  });
+  const ListingPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(MarketplaceListing),
+    'total_count' : IDL.Nat64,
+  });
+  const MapTile = IDL.Record({
+    'tile_x' : IDL.Nat32,
+    'tile_y' : IDL.Nat32,
+    'land_count' : IDL.Nat32,
+    'for_sale' : IDL.Bool,
+    'land_type' : LandType,
+    'land_id' : IDL.Nat64,
+    'owner_hash' : IDL.Nat32,
+  });
+  const MapChunk = IDL.Record({
+    'tiles' : IDL.Vec(MapTile),
+    'chunk_x' : IDL.Int32,
+    'chunk_y' : IDL.Int32,
+    'zoom' : IDL.Nat8,
+    'origin_x' : IDL.Int64,
+    'origin_y' : IDL.Int64,
+    'tile_size' : IDL.Nat32,
+  });
+  const MapChunkResult = IDL.Variant({
+    'Ok' : MapChunk,
+    'Err' : LandRegistryError,
+  });
+  const CertifiedListing = IDL.Record({
+    'certificate' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'witness' : IDL.Vec(IDL.Nat8),
+    'price' : IDL.Opt(IDL.Nat64),
+    'land_id' : IDL.Nat64,
+  });
+  const Offer = IDL.Record({
+    'id' : IDL.Nat64,
+    'made_at' : IDL.Nat64,
+    'buyer' : IDL.Principal,
+    'price' : IDL.Nat64,
+    'land_id' : IDL.Nat64,
+  });
+  const OfferPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(Offer),
+    'total_count' : IDL.Nat64,
+  });
+  const OperatorGrantPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(OperatorGrant),
+    'total_count' : IDL.Nat64,
+  });
+  const PauseState = IDL.Record({
+    'paused_at' : IDL.Nat64,
+    'paused_by' : IDL.Principal,
+    'scope' : PauseScope,
+    'reason' : IDL.Text,
+  });
+  const RezoningStatus = IDL.Variant({
+    'Approved' : IDL.Null,
+    'Rejected' : IDL.Null,
+    'Cancelled' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const RezoningRequest = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : RezoningStatus,
+    'requester' : IDL.Principal,
+    'resolution_note' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'new_type' : LandType,
+    'current_type' : LandType,
+    'land_id' : IDL.Nat64,
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+    'resolved_by' : IDL.Opt(IDL.Principal),
+    'reason' : IDL.Text,
+  });
+  const RezoningRequestPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(RezoningRequest),
+    'total_count' : IDL.Nat64,
+  });
+  const PricePage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(IDL.Tuple(IDL.Nat64, IDL.Nat64)),
+    'total_count' : IDL.Nat64,
+  });
+  const RecoveryRequest = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : RecoveryStatus,
+    'recovered_lands' : IDL.Vec(IDL.Nat64),
+    'owner' : IDL.Principal,
+    'required_approvals' : IDL.Nat32,
+    'new_owner' : IDL.Principal,
+    'executable_after' : IDL.Opt(IDL.Nat64),
+    'approvals' : IDL.Vec(IDL.Principal),
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+    'started_at' : IDL.Nat64,
+  });
+  const RecoveryRequestPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(RecoveryRequest),
+    'total_count' : IDL.Nat64,
+  });
+  const RegistryParameters = IDL.Record({
+    'world_max' : Coordinates,
+    'world_min' : Coordinates,
+  });
+  const Tombstone = IDL.Record({
+    'land' : LandInfo,
+    'purge_after' : IDL.Nat64,
+    'removed_at' : IDL.Nat64,
+    'removed_by' : IDL.Principal,
+    'reason' : IDL.Text,
+  });
+  const TombstonePage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(Tombstone),
+    'total_count' : IDL.Nat64,
+  });
+  const TombstonesResult = IDL.Variant({
+    'Ok' : TombstonePage,
+    'Err' : LandRegistryError,
+  });
+  const RoleAssignment = IDL.Record({
+    'principal' : IDL.Principal,
+    'roles' : IDL.Vec(Role),
+  });
+  const RoleAssignmentsResult = IDL.Variant({
+    'Ok' : IDL.Vec(RoleAssignment),
+    'Err' : LandRegistryError,
+  });
+  const RoleChange = IDL.Record({
+    'principal' : IDL.Principal,
+    'actor' : IDL.Principal,
+    'role' : Role,
+    'granted' : IDL.Bool,
+    'timestamp' : IDL.Nat64,
+  });
+  const RoleChangePage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(RoleChange),
+    'total_count' : IDL.Nat64,
+  });
+  const RoleChangesResult = IDL.Variant({
+    'Ok' : RoleChangePage,
+    'Err' : LandRegistryError,
   });
   const TransactionType = IDL.Variant({
     'Registration' : IDL.Null,
-    'Transfer' : IDL.Null,
     'Sale' : IDL.Null,
+    'Recovery' : IDL.Record({ 'request_id' : IDL.Nat64 }),
+    'ForcedTransfer' : IDL.Record({ 'dispute_id' : IDL.Nat64 }),
+    'Restoration' : IDL.Null,
+    'Transfer' : IDL.Null,
+    'Rezoning' : IDL.Null,
+    'Removal' : IDL.Record({ 'reason' : IDL.Text }),
   });
   const TransactionRecord = IDL.Record({
-    'land_id' : IDL.Nat64,
-    'from' : IDL.Principal,
     'to' : IDL.Principal,
-    'price' : IDL.Opt(IDL.Nat64),
     'transaction_type' : TransactionType,
+    'from' : IDL.Principal,
     'timestamp' : IDL.Nat64,
+    'price' : IDL.Opt(IDL.Nat64),
+    'land_id' : IDL.Nat64,
+  });
+  const TransactionPage = IDL.Record({
+    'next_cursor' : IDL.Opt(PageCursor),
+    'items' : IDL.Vec(TransactionRecord),
+    'total_count' : IDL.Nat64,
+  });
+  const HttpRequest = IDL.Record({
+    'url' : IDL.Text,
+    'method' : IDL.Text,
+    'body' : IDL.Vec(IDL.Nat8),
+    'headers' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Text)),
+  });
+  const HttpResponse = IDL.Record({
+    'body' : IDL.Vec(IDL.Nat8),
+    'headers' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Text)),
+    'status_code' : IDL.Nat16,
+  });
+  const LandRegistration = IDL.Record({
+    'metadata' : IDL.Opt(LandMetadata),
+    'description' : IDL.Text,
+    'land_type' : LandType,
+    'dimensions' : Dimensions,
+    'coordinates' : Coordinates,
+  });
+  const RepairReport = IDL.Record({
+    'found' : IDL.Vec(InvariantIssue),
+    'remaining' : IDL.Vec(InvariantIssue),
+  });
+  const RepairResult = IDL.Variant({
+    'Ok' : RepairReport,
+    'Err' : LandRegistryError,
+  });
+  const FacetCount = IDL.Record({
+    'key' : IDL.Text,
+    'value' : IDL.Text,
+    'count' : IDL.Nat64,
+  });
+  const FacetedLandPage = IDL.Record({
+    'page' : LandPage,
+    'facets' : IDL.Vec(FacetCount),
+  });
+  const SnapshotValidation = IDL.Record({
+    'errors' : IDL.Vec(InvariantIssue),
+    'warnings' : IDL.Vec(InvariantIssue),
+  });
+  const SnapshotValidationResult = IDL.Variant({
+    'Ok' : SnapshotValidation,
+    'Err' : LandRegistryError,
   });
-  
   return IDL.Service({
+    'accept_offer' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
     'add_admin' : IDL.Func([IDL.Principal], [LandRegistryResult_1], []),
+    'approve_admin_action' : IDL.Func(
+        [IDL.Nat64],
+        [AdminProposalStatusResult],
+        [],
+      ),
+    'approve_recovery' : IDL.Func([IDL.Nat64], [RecoveryStatusResult], []),
+    'approve_rezoning_request' : IDL.Func(
+        [IDL.Nat64, IDL.Opt(IDL.Text)],
+        [LandRegistryResult_1],
+        [],
+      ),
     'backup_lands' : IDL.Func([], [IDL.Vec(LandInfo)], ['query']),
-    'buy_land' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
-    'calculate_total_land_area' : IDL.Func([], [IDL.Nat64], ['query']),
-    'get_all_lands' : IDL.Func([], [IDL.Vec(LandInfo)], ['query']),
+    'begin_snapshot_export' : IDL.Func([], [SnapshotManifestResult], []),
+    'begin_snapshot_import' : IDL.Func(
+        [SnapshotManifest],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'buy_land' : IDL.Func([IDL.Nat64, IDL.Nat64], [LandRegistryResult_1], []),
+    'cancel_admin_action' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
+    'cancel_recovery' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
+    'cancel_rezoning_request' : IDL.Func(
+        [IDL.Nat64],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'cancel_snapshot_import' : IDL.Func([], [LandRegistryResult_1], []),
+    'check_invariants' : IDL.Func([], [InvariantCheckResult], ['query']),
+    'clear_guardians' : IDL.Func([], [LandRegistryResult_1], []),
+    'commit_snapshot_import' : IDL.Func([], [LandRegistryResult_1], []),
+    'confirm_activity' : IDL.Func([], [LandRegistryResult_1], []),
+    'create_district' : IDL.Func(
+        [DistrictDefinition],
+        [LandRegistryResult],
+        [],
+      ),
+    'define_attribute' : IDL.Func(
+        [AttributeSchema],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'export_geojson' : IDL.Func(
+        [IDL.Opt(SearchFilters), PageRequest],
+        [GeoJsonExport],
+        ['query'],
+      ),
+    'export_gltf' : IDL.Func(
+        [IDL.Opt(SearchFilters), PageRequest],
+        [GltfExport],
+        ['query'],
+      ),
+    'export_snapshot_chunk' : IDL.Func(
+        [IDL.Nat64, IDL.Nat32],
+        [SnapshotChunkResult],
+        ['query'],
+      ),
+    'find_free_space' : IDL.Func(
+        [Dimensions, LandType, IDL.Tuple(Coordinates, Coordinates), IDL.Nat32],
+        [FreeSpaceResult],
+        ['query'],
+      ),
+    'finish_snapshot_export' : IDL.Func(
+        [IDL.Nat64],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'get_admin_proposals' : IDL.Func(
+        [IDL.Bool, PageRequest],
+        [AdminProposalsResult],
+        ['query'],
+      ),
+    'get_all_lands' : IDL.Func([PageRequest], [LandPage], ['query']),
+    'get_approval_threshold' : IDL.Func([], [IDL.Nat32], ['query']),
+    'get_attribute_schemas' : IDL.Func(
+        [],
+        [IDL.Vec(AttributeSchema)],
+        ['query'],
+      ),
+    'get_audit_log' : IDL.Func(
+        [AuditFilter, PageRequest],
+        [AuditPageResult],
+        ['query'],
+      ),
+    'get_dispute' : IDL.Func([IDL.Nat64], [IDL.Opt(Dispute)], ['query']),
+    'get_disputes_for_land' : IDL.Func(
+        [IDL.Nat64, PageRequest],
+        [DisputePage],
+        ['query'],
+      ),
+    'get_district' : IDL.Func([IDL.Nat64], [IDL.Opt(District)], ['query']),
+    'get_districts' : IDL.Func([PageRequest], [DistrictPage], ['query']),
+    'get_governance_config' : IDL.Func([], [GovernanceConfig], ['query']),
+    'get_governance_proposal' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Opt(GovernanceProposal)],
+        ['query'],
+      ),
+    'get_governance_proposals' : IDL.Func(
+        [IDL.Bool, PageRequest],
+        [GovernanceProposalPage],
+        ['query'],
+      ),
+    'get_guardians' : IDL.Func(
+        [IDL.Principal],
+        [IDL.Opt(GuardianConfig)],
+        ['query'],
+      ),
     'get_land' : IDL.Func([IDL.Nat64], [IDL.Opt(LandInfo)], ['query']),
-    'get_land_count_by_owner' : IDL.Func([IDL.Principal], [IDL.Nat64], ['query']),
-    'get_land_owner' : IDL.Func([IDL.Nat64], [IDL.Opt(IDL.Principal)], ['query']),
+    'get_land_district' : IDL.Func([IDL.Nat64], [IDL.Opt(District)], ['query']),
+    'get_land_operators' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Vec(OperatorGrant)],
+        ['query'],
+      ),
+    'get_land_owner' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Opt(IDL.Principal)],
+        ['query'],
+      ),
+    'get_land_owner_certified' : IDL.Func(
+        [IDL.Nat64],
+        [CertifiedOwner],
+        ['query'],
+      ),
     'get_land_statistics' : IDL.Func([], [LandStatistics], ['query']),
-    'get_lands_by_owner' : IDL.Func([IDL.Principal], [IDL.Vec(LandInfo)], ['query']),
-    'get_lands_by_type' : IDL.Func([LandType], [IDL.Vec(LandInfo)], ['query']),
-    'get_lands_for_sale_by_type' : IDL.Func([LandType], [IDL.Vec(MarketplaceListing)], ['query']),
-    'get_lands_near_coordinates' : IDL.Func([Coordinates, IDL.Nat32], [IDL.Vec(LandInfo)], ['query']),
-    'get_marketplace_listing' : IDL.Func([IDL.Nat64], [IDL.Opt(MarketplaceListing)], ['query']),
-    'get_marketplace_listings' : IDL.Func([], [IDL.Vec(MarketplaceListing)], ['query']),
+    'get_lands_by_owner' : IDL.Func(
+        [IDL.Principal, PageRequest],
+        [LandPage],
+        ['query'],
+      ),
+    'get_lands_for_sale_by_type' : IDL.Func(
+        [LandType, PageRequest],
+        [ListingPage],
+        ['query'],
+      ),
+    'get_lands_near_coordinates' : IDL.Func(
+        [Coordinates, IDL.Nat32, PageRequest],
+        [LandPage],
+        ['query'],
+      ),
+    'get_listings_by_listed_at' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64, PageRequest],
+        [ListingPage],
+        ['query'],
+      ),
+    'get_listings_by_price_band' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64, PageRequest],
+        [ListingPage],
+        ['query'],
+      ),
+    'get_map_chunk' : IDL.Func(
+        [IDL.Int32, IDL.Int32, IDL.Nat8],
+        [MapChunkResult],
+        ['query'],
+      ),
+    'get_marketplace_listing' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Opt(MarketplaceListing)],
+        ['query'],
+      ),
+    'get_marketplace_listing_certified' : IDL.Func(
+        [IDL.Nat64],
+        [CertifiedListing],
+        ['query'],
+      ),
+    'get_marketplace_listings' : IDL.Func(
+        [PageRequest],
+        [ListingPage],
+        ['query'],
+      ),
+    'get_my_roles' : IDL.Func([], [IDL.Vec(Role)], ['query']),
     'get_next_land_id' : IDL.Func([], [IDL.Nat64], ['query']),
-    'get_price_history' : IDL.Func([IDL.Nat64], [IDL.Vec(IDL.Tuple(IDL.Nat64, IDL.Nat64))], ['query']),
-    'get_recent_transactions' : IDL.Func([IDL.Nat64], [IDL.Vec(TransactionRecord)], ['query']),
+    'get_offers_for_land' : IDL.Func(
+        [IDL.Nat64, PageRequest],
+        [OfferPage],
+        ['query'],
+      ),
+    'get_open_disputes' : IDL.Func([PageRequest], [DisputePage], ['query']),
+    'get_operated_lands' : IDL.Func(
+        [IDL.Principal, PageRequest],
+        [OperatorGrantPage],
+        ['query'],
+      ),
+    'get_pause_status' : IDL.Func([], [IDL.Vec(PauseState)], ['query']),
+    'get_pending_rezoning_requests' : IDL.Func(
+        [PageRequest],
+        [RezoningRequestPage],
+        ['query'],
+      ),
+    'get_price_history' : IDL.Func(
+        [IDL.Nat64, PageRequest],
+        [PricePage],
+        ['query'],
+      ),
+    'get_recovery_request' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Opt(RecoveryRequest)],
+        ['query'],
+      ),
+    'get_recovery_requests_for_owner' : IDL.Func(
+        [IDL.Principal, PageRequest],
+        [RecoveryRequestPage],
+        ['query'],
+      ),
+    'get_registry_parameters' : IDL.Func([], [RegistryParameters], ['query']),
+    'get_removed_lands' : IDL.Func(
+        [PageRequest],
+        [TombstonesResult],
+        ['query'],
+      ),
+    'get_rezoning_request' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Opt(RezoningRequest)],
+        ['query'],
+      ),
+    'get_rezoning_requests_for_land' : IDL.Func(
+        [IDL.Nat64, PageRequest],
+        [RezoningRequestPage],
+        ['query'],
+      ),
+    'get_role_assignments' : IDL.Func([], [RoleAssignmentsResult], ['query']),
+    'get_role_changes' : IDL.Func(
+        [PageRequest],
+        [RoleChangesResult],
+        ['query'],
+      ),
+    'get_roles' : IDL.Func([IDL.Principal], [IDL.Vec(Role)], ['query']),
     'get_total_supply' : IDL.Func([], [IDL.Nat64], ['query']),
-    'get_transaction_history' : IDL.Func([IDL.Opt(IDL.Nat64)], [IDL.Vec(TransactionRecord)], ['query']),
-    'get_user_transactions' : IDL.Func([IDL.Principal], [IDL.Vec(TransactionRecord)], ['query']),
+    'get_transaction_history' : IDL.Func(
+        [IDL.Opt(IDL.Nat64), PageRequest],
+        [TransactionPage],
+        ['query'],
+      ),
+    'get_user_transactions' : IDL.Func(
+        [IDL.Principal, PageRequest],
+        [TransactionPage],
+        ['query'],
+      ),
+    'get_voting_power' : IDL.Func([IDL.Principal], [IDL.Nat64], ['query']),
+    'grant_operator' : IDL.Func(
+        [
+          IDL.Nat64,
+          IDL.Principal,
+          IDL.Vec(OperatorPermission),
+          IDL.Opt(IDL.Nat64),
+        ],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'grant_role' : IDL.Func([IDL.Principal, Role], [LandRegistryResult_1], []),
+    'http_request' : IDL.Func([HttpRequest], [HttpResponse], ['query']),
+    'import_snapshot_chunk' : IDL.Func(
+        [IDL.Nat32, IDL.Vec(IDL.Nat8)],
+        [LandRegistryResult_1],
+        [],
+      ),
     'is_admin' : IDL.Func([IDL.Principal], [IDL.Bool], ['query']),
-    'list_for_sale' : IDL.Func([IDL.Nat64, IDL.Nat64], [LandRegistryResult_1], []),
+    'list_for_sale' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'make_offer' : IDL.Func([IDL.Nat64, IDL.Nat64], [LandRegistryResult], []),
+    'open_dispute' : IDL.Func([IDL.Nat64, IDL.Text], [LandRegistryResult], []),
+    'pause' : IDL.Func([PauseScope, IDL.Text], [LandRegistryResult_1], []),
+    'place_hold' : IDL.Func(
+        [IDL.Nat64, HoldKind, IDL.Text, IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64)],
+        [LandRegistryResult],
+        [],
+      ),
+    'propose_admin_action' : IDL.Func([AdminAction], [LandRegistryResult], []),
     'register_land' : IDL.Func([LandRegistration], [LandRegistryResult], []),
+    'reject_rezoning_request' : IDL.Func(
+        [IDL.Nat64, IDL.Opt(IDL.Text)],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'release_hold' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'remove_attribute_schema' : IDL.Func(
+        [IDL.Text],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'remove_district' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
     'remove_from_sale' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
-    'remove_land' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
+    'remove_land' : IDL.Func([IDL.Nat64, IDL.Text], [LandRegistryResult_1], []),
+    'repair' : IDL.Func([], [RepairResult], []),
+    'request_land_type_change' : IDL.Func(
+        [IDL.Nat64, LandType, IDL.Text],
+        [LandRegistryResult],
+        [],
+      ),
+    'resolve_dispute' : IDL.Func(
+        [IDL.Nat64, DisputeOutcome, IDL.Opt(IDL.Text)],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'restore_land' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
     'restore_lands' : IDL.Func([IDL.Vec(LandInfo)], [LandRegistryResult_1], []),
-    'search_by_coordinates' : IDL.Func([Coordinates, Coordinates], [IDL.Vec(LandInfo)], ['query']),
-    'search_lands' : IDL.Func([SearchFilters], [IDL.Vec(LandInfo)], ['query']),
-    'search_marketplace' : IDL.Func([SearchFilters], [IDL.Vec(MarketplaceListing)], ['query']),
-    'transfer_land' : IDL.Func([IDL.Nat64, IDL.Principal], [LandRegistryResult_1], []),
-    'update_land_metadata' : IDL.Func([IDL.Nat64, LandMetadata], [LandRegistryResult_1], []),
-    'verify_land_ownership' : IDL.Func([IDL.Nat64, IDL.Principal], [IDL.Bool], ['query']),
+    'revoke_operator' : IDL.Func(
+        [IDL.Nat64, IDL.Principal],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'revoke_role' : IDL.Func([IDL.Principal, Role], [LandRegistryResult_1], []),
+    'search_by_coordinates' : IDL.Func(
+        [Coordinates, Coordinates, PageRequest],
+        [LandPage],
+        ['query'],
+      ),
+    'search_lands' : IDL.Func(
+        [SearchFilters, PageRequest],
+        [LandPage],
+        ['query'],
+      ),
+    'search_lands_faceted' : IDL.Func(
+        [SearchFilters, PageRequest],
+        [FacetedLandPage],
+        ['query'],
+      ),
+    'search_marketplace' : IDL.Func(
+        [SearchFilters, PageRequest],
+        [ListingPage],
+        ['query'],
+      ),
+    'search_text' : IDL.Func([IDL.Text, PageRequest], [LandPage], ['query']),
+    'set_guardians' : IDL.Func(
+        [IDL.Vec(IDL.Principal), IDL.Nat32, IDL.Nat64, IDL.Nat64],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'start_recovery' : IDL.Func(
+        [IDL.Principal, IDL.Principal],
+        [LandRegistryResult],
+        [],
+      ),
+    'submit_dispute_evidence' : IDL.Func(
+        [IDL.Nat64, IDL.Text],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'submit_governance_proposal' : IDL.Func(
+        [IDL.Text, IDL.Text, GovernanceAction],
+        [LandRegistryResult],
+        [],
+      ),
+    'transfer_land' : IDL.Func(
+        [IDL.Nat64, IDL.Principal],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'unpause' : IDL.Func([PauseScope], [LandRegistryResult_1], []),
+    'update_district' : IDL.Func(
+        [IDL.Nat64, DistrictDefinition],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'update_land_metadata' : IDL.Func(
+        [IDL.Nat64, LandMetadata],
+        [LandRegistryResult_1],
+        [],
+      ),
+    'validate_snapshot' : IDL.Func(
+        [IDL.Vec(LandInfo)],
+        [SnapshotValidationResult],
+        ['query'],
+      ),
+    'verify_land_ownership' : IDL.Func(
+        [IDL.Nat64, IDL.Principal],
+        [IDL.Bool],
+        ['query'],
+      ),
+    'vote_on_proposal' : IDL.Func(
+        [IDL.Nat64, IDL.Bool],
+        [LandRegistryResult],
+        [],
+      ),
+    'withdraw_offer' : IDL.Func([IDL.Nat64], [LandRegistryResult_1], []),
   });
 };
 export const init = ({ IDL }) => { return []; };
\ No newline at end of file
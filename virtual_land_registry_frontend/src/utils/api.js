@@ -1,5 +1,38 @@
 import { virtual_land_registry_backend } from '../declarations/virtual_land_registry_backend';
 
+// List queries take a PageRequest and return { items, next_cursor, total_count }.
+// Follows next_cursor until every page has been read.
+export const fetchAllPages = async (fetchPage, limit = 500) => {
+  const items = [];
+  let cursor = [];
+  do {
+    const page = await fetchPage({ cursor, limit: [limit], sort_by: [], direction: [] });
+    items.push(...page.items);
+    cursor = page.next_cursor;
+  } while (cursor.length > 0);
+  return items;
+};
+
+// Every SearchFilters field is optional; callers override the ones they use
+export const emptySearchFilters = () => ({
+  land_type: [],
+  min_price: [],
+  max_price: [],
+  coordinates_range: [],
+  min_area: [],
+  features: [],
+  owner: [],
+  created_after: [],
+  created_before: [],
+  max_area: [],
+  environment: [],
+  utilities: [],
+  access_roads: [],
+  for_sale_only: [],
+  text: [],
+  attributes: [],
+});
+
 // API wrapper functions for better error handling and consistency
 
 export const landAPI = {
@@ -22,7 +55,7 @@ export const landAPI = {
   // Get all lands
   getAllLands: async () => {
     try {
-      const result = await virtual_land_registry_backend.get_all_lands();
+      const result = await fetchAllPages(page => virtual_land_registry_backend.get_all_lands(page));
       return { success: true, data: result };
     } catch (error) {
       console.error('Failed to fetch all lands:', error);
@@ -48,7 +81,7 @@ export const landAPI = {
   // Get lands by owner
   getLandsByOwner: async (owner) => {
     try {
-      const result = await virtual_land_registry_backend.get_lands_by_owner(owner);
+      const result = await fetchAllPages(page => virtual_land_registry_backend.get_lands_by_owner(owner, page));
       return { success: true, data: result };
     } catch (error) {
       console.error('Failed to fetch lands by owner:', error);
@@ -70,8 +103,8 @@ export const landAPI = {
   // Get lands for sale
   getLandsForSale: async () => {
     try {
-      const allLands = await virtual_land_registry_backend.get_all_lands();
-      const forSale = allLands.filter(land => land.for_sale);
+      const listings = await fetchAllPages(page => virtual_land_registry_backend.get_marketplace_listings(page));
+      const forSale = listings.map(listing => listing.land_info);
       return { success: true, data: forSale };
     } catch (error) {
       console.error('Failed to fetch lands for sale:', error);